@@ -0,0 +1,46 @@
+use std::fs::{read_dir, write};
+use std::path::Path;
+
+/// Declares a module for every `src/yearYYYY/dayN.rs` file, so adding a day
+/// only means adding its file.
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let mut years = list_modules(&src, "year");
+    years.sort();
+
+    let mut modules = String::new();
+    for (_, year) in years {
+        let year_dir = src.join(&year);
+        println!("cargo:rerun-if-changed={}", year_dir.display());
+
+        let mut days = list_modules(&year_dir, "day");
+        days.sort();
+
+        modules.push_str(&format!("pub mod {} {{\n", year));
+        for (_, day) in days {
+            let path = year_dir.join(format!("{}.rs", day));
+            modules.push_str(&format!("    #[path = {:?}]\n    mod {};\n", path, day));
+        }
+        modules.push_str("}\n");
+    }
+
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR not set");
+    write(Path::new(&out_dir).join("days.rs"), modules).expect("Failed to write day modules");
+}
+
+/// Finds the entries in `dir` named like `{prefix}N` or `{prefix}N.rs`,
+/// along with their number so they sort in order.
+fn list_modules(dir: &Path, prefix: &str) -> Vec<(u32, String)> {
+    read_dir(dir)
+        .expect("Failed to read source folder")
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let module = name.strip_suffix(".rs").unwrap_or(&name);
+            let number = module.strip_prefix(prefix)?.parse().ok()?;
+
+            Some((number, module.to_owned()))
+        })
+        .collect()
+}
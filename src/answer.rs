@@ -0,0 +1,173 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Unsigned(u128),
+    Signed(i128),
+    Text(String),
+    /// Rows of characters, e.g. the `#`/`.` drawings some puzzles produce.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// A single-line rendering of the answer, for use in tables.
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Grid(rows) => format!(
+                "<{}x{} grid>",
+                rows.iter()
+                    .map(|row| row.chars().count())
+                    .max()
+                    .unwrap_or(0),
+                rows.len()
+            ),
+            other => other.to_string(),
+        }
+    }
+
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            Self::Unsigned(value) => i128::try_from(*value).ok(),
+            Self::Signed(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsigned(value) => write!(f, "{}", value),
+            Self::Signed(value) => write!(f, "{}", value),
+            Self::Text(text) => write!(f, "{}", text),
+            Self::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+/// Integers compare by value regardless of width or signedness, and
+/// grids ignore trailing whitespace on each row.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Unsigned(a), Self::Unsigned(b)) => a == b,
+            (Self::Text(a), Self::Text(b)) => a.trim() == b.trim(),
+            (Self::Grid(a), Self::Grid(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.trim_end() == b.trim_end())
+            }
+            (a, b) => match (a.as_integer(), b.as_integer()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+/// Parses a stored answer: integers become numbers, multi-line text
+/// becomes a grid, and anything else is kept as text.
+impl FromStr for Answer {
+    type Err = anyhow::Error;
+
+    fn from_str(answer: &str) -> anyhow::Result<Self> {
+        let answer = answer.trim();
+
+        Ok(if let Ok(value) = answer.parse() {
+            Self::Unsigned(value)
+        } else if let Ok(value) = answer.parse() {
+            Self::Signed(value)
+        } else if answer.contains('\n') {
+            Self::Grid(answer.lines().map(String::from).collect())
+        } else {
+            Self::Text(answer.to_owned())
+        })
+    }
+}
+
+macro_rules! impl_from_int {
+    ($variant:ident, $wide:ty, $($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(value: $int) -> Self {
+                    Self::$variant(value as $wide)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(Unsigned, u128, u8, u16, u32, u64, u128, usize);
+impl_from_int!(Signed, i128, i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_owned())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(rows: Vec<String>) -> Self {
+        Self::Grid(rows)
+    }
+}
+
+impl From<Vec<Vec<char>>> for Answer {
+    fn from(rows: Vec<Vec<char>>) -> Self {
+        Self::Grid(
+            rows.into_iter()
+                .map(|row| row.into_iter().collect())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn compares_integers_across_widths_and_signs() {
+        assert_eq!(Answer::from(42_u8), Answer::from(42_i64));
+        assert_eq!(Answer::from(42_usize), Answer::Signed(42));
+        assert_ne!(Answer::from(42_u32), Answer::from(-42_i32));
+        assert_ne!(Answer::Unsigned(u128::MAX), Answer::Signed(-1));
+        assert_ne!(Answer::from(42_u32), Answer::from("42"));
+    }
+
+    #[test]
+    fn ignores_surrounding_whitespace() {
+        assert_eq!(Answer::from(" ABC\n"), Answer::from("ABC"));
+        assert_eq!(
+            Answer::from(vec!["#..#  ".to_owned(), "####".to_owned()]),
+            Answer::from(vec!["#..#".to_owned(), "#### ".to_owned()])
+        );
+        assert_ne!(
+            Answer::from(vec!["#..#".to_owned()]),
+            Answer::from(vec!["#..#".to_owned(), "####".to_owned()])
+        );
+        assert_ne!(
+            Answer::from(vec![" #".to_owned()]),
+            Answer::from(vec!["#".to_owned()])
+        );
+    }
+
+    #[test]
+    fn parses_stored_answers() {
+        assert!(matches!("1656".parse(), Ok(Answer::Unsigned(1656))));
+        assert!(matches!(" -12 ".parse(), Ok(Answer::Signed(-12))));
+        assert!(matches!("ABCDEFGH".parse(), Ok(Answer::Text(text)) if text == "ABCDEFGH"));
+        assert!(matches!(
+            "#..#\n####\n".parse(),
+            Ok(Answer::Grid(rows)) if rows == ["#..#", "####"]
+        ));
+    }
+}
@@ -1,35 +1,328 @@
+use std::str::FromStr;
+
+use anyhow::Context;
+
+use crate::answer::Answer;
+
+/// A day that works on its raw input, parsing it inside each part if
+/// it needs to. Days that implement [`Solution`] get this for free.
 pub trait Aoc {
-    fn part1(&self, input: &str) -> anyhow::Result<usize>;
-    fn part2(&self, input: &str) -> anyhow::Result<usize>;
-}
-
-pub fn get_day(day: usize) -> anyhow::Result<Box<dyn Aoc>> {
-    match day {
-        1 => Ok(Box::new(crate::day1::Day1)),
-        2 => Ok(Box::new(crate::day2::Day2)),
-        3 => Ok(Box::new(crate::day3::Day3)),
-        4 => Ok(Box::new(crate::day4::Day4)),
-        5 => Ok(Box::new(crate::day5::Day5)),
-        6 => Ok(Box::new(crate::day6::Day6)),
-        7 => Ok(Box::new(crate::day7::Day7)),
-        8 => Ok(Box::new(crate::day8::Day8)),
-        9 => Ok(Box::new(crate::day9::Day9)),
-        10 => Ok(Box::new(crate::day10::Day10)),
-        11 => Ok(Box::new(crate::day11::Day11)),
-        12 => Ok(Box::new(crate::day12::Day12)),
-        13 => Ok(Box::new(crate::day13::Day13)),
-        14 => Ok(Box::new(crate::day14::Day14)),
-        15 => Ok(Box::new(crate::day15::Day15)),
-        16 => Ok(Box::new(crate::day16::Day16)),
-        17 => Ok(Box::new(crate::day17::Day17)),
-        18 => Ok(Box::new(crate::day18::Day18)),
-        19 => Ok(Box::new(crate::day19::Day19)),
-        20 => Ok(Box::new(crate::day20::Day20)),
-        21 => Ok(Box::new(crate::day21::Day21)),
-        22 => Ok(Box::new(crate::day22::Day22)),
-        23 => Ok(Box::new(crate::day23::Day23)),
-        24 => Ok(Box::new(crate::day24::Day24)),
-        25 => Ok(Box::new(crate::day25::Day25)),
-        _ => Err(anyhow::anyhow!("Unsupported day: {}", day)),
+    fn part1(&self, _input: &str) -> anyhow::Result<Answer> {
+        Err(NotImplemented.into())
+    }
+
+    fn part2(&self, _input: &str) -> anyhow::Result<Answer> {
+        Err(NotImplemented.into())
+    }
+
+    /// The examples from the puzzle description, with their expected answers.
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    fn solve(&self, part: Part, input: &str) -> anyhow::Result<Answer> {
+        match part {
+            Part::One => self.part1(input),
+            Part::Two => self.part2(input),
+        }
+    }
+
+    /// Parses the input once so both parts can share it. Days that don't
+    /// parse up front just hold on to the raw input.
+    fn prepare<'a>(&'a self, input: &'a str) -> anyhow::Result<Box<dyn Prepared + 'a>> {
+        Ok(Box::new(RawInput { day: self, input }))
+    }
+}
+
+/// A day's input, ready to be solved.
+pub trait Prepared {
+    fn solve(&self, part: Part) -> anyhow::Result<Answer>;
+
+    /// Whether parsing happened up front, and so is worth timing.
+    fn is_parsed(&self) -> bool;
+}
+
+struct RawInput<'a, D: ?Sized> {
+    day: &'a D,
+    input: &'a str,
+}
+
+impl<D: Aoc + ?Sized> Prepared for RawInput<'_, D> {
+    fn solve(&self, part: Part) -> anyhow::Result<Answer> {
+        self.day.solve(part, self.input)
+    }
+
+    fn is_parsed(&self) -> bool {
+        false
+    }
+}
+
+/// A day that parses its input once and passes the result to both parts,
+/// so parsing can be timed separately from solving. The parsed input can
+/// borrow from the raw input.
+pub trait Solution {
+    type Parsed<'a>;
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>>;
+
+    fn part1(&self, _parsed: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        Err(NotImplemented.into())
+    }
+
+    fn part2(&self, _parsed: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        Err(NotImplemented.into())
+    }
+
+    /// The examples from the puzzle description, with their expected answers.
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+}
+
+struct ParsedInput<'a, S: Solution> {
+    solution: &'a S,
+    parsed: S::Parsed<'a>,
+}
+
+impl<S: Solution> Prepared for ParsedInput<'_, S> {
+    fn solve(&self, part: Part) -> anyhow::Result<Answer> {
+        match part {
+            Part::One => self.solution.part1(&self.parsed),
+            Part::Two => self.solution.part2(&self.parsed),
+        }
+    }
+
+    fn is_parsed(&self) -> bool {
+        true
+    }
+}
+
+impl<S: Solution> Aoc for S {
+    fn part1(&self, input: &str) -> anyhow::Result<Answer> {
+        Solution::part1(self, &self.parse(input)?)
+    }
+
+    fn part2(&self, input: &str) -> anyhow::Result<Answer> {
+        Solution::part2(self, &self.parse(input)?)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        Solution::examples(self)
+    }
+
+    fn prepare<'a>(&'a self, input: &'a str) -> anyhow::Result<Box<dyn Prepared + 'a>> {
+        Ok(Box::new(ParsedInput {
+            solution: self,
+            parsed: self.parse(input)?,
+        }))
+    }
+}
+
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+}
+
+impl Example {
+    pub fn expected(&self, part: Part) -> anyhow::Result<Option<Answer>> {
+        let expected = match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        };
+
+        expected
+            .map(|answer| {
+                answer
+                    .parse()
+                    .with_context(|| format!("Invalid expected answer for part {}", part))
+            })
+            .transpose()
+    }
+}
+
+/// Generates a unit test per part that checks a day against its examples.
+macro_rules! example_tests {
+    ($day:expr) => {
+        #[cfg(test)]
+        mod example_tests {
+            use super::*;
+            use crate::aoc::{check_examples, Part};
+
+            #[test]
+            fn part1() {
+                check_examples(&$day, Part::One);
+            }
+
+            #[test]
+            fn part2() {
+                check_examples(&$day, Part::Two);
+            }
+        }
+    };
+}
+
+pub(crate) use example_tests;
+
+#[cfg(test)]
+pub fn check_examples(day: &dyn Aoc, part: Part) {
+    for (index, example) in day.examples().iter().enumerate() {
+        let expected = example
+            .expected(part)
+            .unwrap_or_else(|error| panic!("Example {}: {:#}", index + 1, error));
+        if let Some(expected) = expected {
+            let actual = day
+                .solve(part, example.input)
+                .unwrap_or_else(|error| panic!("Example {} failed: {:#}", index + 1, error));
+            assert_eq!(
+                actual,
+                expected,
+                "Example {} gave the wrong answer",
+                index + 1
+            );
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> usize {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(part: &str) -> anyhow::Result<Self> {
+        match part.trim() {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => anyhow::bail!("Part must be 1 or 2, got {}", part),
+        }
+    }
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Returned by a part that hasn't been solved yet, which the runner
+/// reports without treating it as a failure.
+#[derive(Debug)]
+pub struct NotImplemented;
+
+impl std::fmt::Display for NotImplemented {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "not implemented")
+    }
+}
+
+impl std::error::Error for NotImplemented {}
+
+/// A day's solution along with what's known about it, registered from the
+/// day's own file with [`register_day!`].
+pub struct DayEntry {
+    pub year: i32,
+    pub day: usize,
+    pub title: &'static str,
+    pub parts: &'static [Part],
+    pub new: fn() -> Box<dyn Aoc>,
+    /// The module the day was registered from, to check it's filed under
+    /// the year and day it claims to be.
+    #[cfg(test)]
+    pub module: &'static str,
+}
+
+inventory::collect!(DayEntry);
+
+/// Registers a day so the runner can find it, e.g.
+///
+/// ```ignore
+/// crate::aoc::register_day! {
+///     year: 2021, day: 1, title: "Sonar Sweep", parts: [One, Two], solution: Day1
+/// }
+/// ```
+macro_rules! register_day {
+    (
+        year: $year:expr,
+        day: $day:expr,
+        title: $title:expr,
+        parts: [$($part:ident),*],
+        solution: $solution:expr $(,)?
+    ) => {
+        inventory::submit! {
+            crate::aoc::DayEntry {
+                year: $year,
+                day: $day,
+                title: $title,
+                parts: &[$(crate::aoc::Part::$part),*],
+                new: || Box::new($solution),
+                #[cfg(test)]
+                module: module_path!(),
+            }
+        }
+    };
+}
+
+pub(crate) use register_day;
+
+/// Every registered day of the given year, in order.
+pub fn days(year: i32) -> Vec<&'static DayEntry> {
+    let mut days: Vec<&DayEntry> = inventory::iter::<DayEntry>
+        .into_iter()
+        .filter(|entry| entry.year == year)
+        .collect();
+    days.sort_by_key(|entry| entry.day);
+
+    days
+}
+
+pub fn latest_year() -> i32 {
+    inventory::iter::<DayEntry>
+        .into_iter()
+        .map(|entry| entry.year)
+        .max()
+        .unwrap_or(2021)
+}
+
+pub fn get_day(year: i32, day: usize) -> anyhow::Result<Box<dyn Aoc>> {
+    let days = days(year);
+    if days.is_empty() {
+        anyhow::bail!("Unsupported year: {}", year);
+    }
+
+    days.iter()
+        .find(|entry| entry.day == day)
+        .map(|entry| (entry.new)())
+        .ok_or_else(|| anyhow::anyhow!("Unsupported day: {}", day))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn days_are_registered_from_their_own_modules() {
+        for entry in inventory::iter::<super::DayEntry> {
+            let expected = format!("::year{}::day{}", entry.year, entry.day);
+            assert!(
+                entry.module.ends_with(&expected),
+                "{} registers {} day {}",
+                entry.module,
+                entry.year,
+                entry.day
+            );
+        }
     }
 }
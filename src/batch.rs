@@ -0,0 +1,249 @@
+use std::fs::{read_dir, read_to_string};
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Context;
+
+use crate::answer::Answer;
+use crate::aoc::{self, Part};
+use crate::runner::{self, PartOutcome, PartResult, Solver};
+use crate::sandbox::Limits;
+use crate::verify::ExpectedDay;
+
+/// One input from the batch, named after its file, along with the answers
+/// it's expected to give.
+pub struct BatchInput {
+    pub name: String,
+    pub input: String,
+    pub expected: ExpectedDay,
+}
+
+pub struct BatchOutcome {
+    pub name: String,
+    pub expected: [Option<Answer>; 2],
+    pub parse: Option<Duration>,
+    pub parts: anyhow::Result<[PartOutcome; 2]>,
+}
+
+impl BatchOutcome {
+    /// What went wrong with each part, either not getting an answer or
+    /// getting one other than expected.
+    pub fn problems(&self) -> Vec<String> {
+        let parts = match &self.parts {
+            Ok(parts) => parts,
+            Err(error) => return vec![format!("error: {:#}", error)],
+        };
+
+        Part::BOTH
+            .iter()
+            .zip(parts)
+            .zip(&self.expected)
+            .filter_map(|((part, outcome), expected)| {
+                let problem = match (outcome.result.answer(), expected) {
+                    // Nothing's missing if there was nothing to check
+                    (None, None) if matches!(outcome.result, PartResult::NotImplemented) => {
+                        return None
+                    }
+                    (None, _) => outcome.result.to_string(),
+                    (Some(answer), Some(expected)) if answer != expected => {
+                        format!("expected {}", expected.summary())
+                    }
+                    _ => return None,
+                };
+
+                Some(format!("part {}: {}", part, problem))
+            })
+            .collect()
+    }
+}
+
+/// Loads every `*.txt` input in a folder, with the expected answers for
+/// `name.txt` read from `name.toml` next to it if there is one:
+///
+/// ```toml
+/// part1 = 1387
+/// part2 = 1362
+/// ```
+pub fn load_inputs(folder: &Path) -> anyhow::Result<Vec<BatchInput>> {
+    let mut paths: Vec<PathBuf> = read_dir(folder)
+        .with_context(|| format!("Failed to read inputs from {}", folder.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()
+        .context("Failed to read inputs")?;
+    paths.retain(|path| path.extension().is_some_and(|extension| extension == "txt"));
+    paths.sort();
+
+    if paths.is_empty() {
+        anyhow::bail!("No *.txt inputs in {}", folder.display());
+    }
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let input = read_to_string(&path)
+                .with_context(|| format!("Failed to read input from {}", path.display()))?;
+
+            let answers_path = path.with_extension("toml");
+            let expected = if answers_path.exists() {
+                let contents = read_to_string(&answers_path).with_context(|| {
+                    format!("Failed to read answers from {}", answers_path.display())
+                })?;
+
+                toml::from_str(&contents).with_context(|| {
+                    format!("Failed to parse answers from {}", answers_path.display())
+                })?
+            } else {
+                ExpectedDay::default()
+            };
+
+            Ok(BatchInput {
+                name: path
+                    .file_stem()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                input,
+                expected,
+            })
+        })
+        .collect()
+}
+
+/// Runs a day on every input, spread across as many threads as there are
+/// cores.
+pub fn run_batch(
+    year: i32,
+    day: usize,
+    inputs: &[BatchInput],
+    limits: &Limits,
+) -> anyhow::Result<Vec<BatchOutcome>> {
+    aoc::get_day(year, day)?;
+
+    let threads = thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(inputs.len())
+        .max(1);
+    let chunk_size = ((inputs.len() + threads - 1) / threads).max(1);
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = inputs
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let handle = scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|input| {
+                            panic::catch_unwind(AssertUnwindSafe(|| {
+                                run_input(year, day, input, limits)
+                            }))
+                            .unwrap_or_else(|_| Ok(panicked(input)))
+                        })
+                        .collect::<Vec<_>>()
+                });
+
+                (chunk, handle)
+            })
+            .collect();
+
+        let mut outcomes = Vec::with_capacity(inputs.len());
+        for (chunk, handle) in handles {
+            match handle.join() {
+                Ok(results) => {
+                    for result in results {
+                        outcomes.push(result?);
+                    }
+                }
+                Err(_) => outcomes.extend(chunk.iter().map(panicked)),
+            }
+        }
+
+        Ok(outcomes)
+    })
+}
+
+/// The row for an input whose run panicked outside of its parts.
+fn panicked(input: &BatchInput) -> BatchOutcome {
+    BatchOutcome {
+        name: input.name.clone(),
+        expected: [None, None],
+        parse: None,
+        parts: Err(anyhow::anyhow!(
+            "The batch thread panicked running this input"
+        )),
+    }
+}
+
+fn run_input(
+    year: i32,
+    day: usize,
+    input: &BatchInput,
+    limits: &Limits,
+) -> anyhow::Result<BatchOutcome> {
+    let expected = [
+        input.expected.get(Part::One)?,
+        input.expected.get(Part::Two)?,
+    ];
+
+    let day_impl = aoc::get_day(year, day)?;
+    let mut parse = None;
+    let parts =
+        Solver::new(year, day, day_impl.as_ref(), &input.input, *limits).map(|mut solver| {
+            let parts = solver.solve_both();
+            parse = solver.parse;
+
+            parts
+        });
+
+    Ok(BatchOutcome {
+        name: input.name.clone(),
+        expected,
+        parse,
+        parts,
+    })
+}
+
+pub fn print_batch(outcomes: &[BatchOutcome]) {
+    runner::print_part_table("Input", outcomes.iter().map(batch_row).collect());
+}
+
+fn batch_row(outcome: &BatchOutcome) -> [String; 9] {
+    let parse = outcome
+        .parse
+        .map(runner::format_duration)
+        .unwrap_or_else(|| "-".to_owned());
+    let problems = outcome.problems();
+    let status = if !problems.is_empty() {
+        problems.join("; ")
+    } else if outcome.expected.iter().all(Option::is_none) {
+        "no answers".to_owned()
+    } else {
+        "ok".to_owned()
+    };
+
+    let dash = || "-".to_owned();
+    let [(answer1, time1, memory1), (answer2, time2, memory2)] = match &outcome.parts {
+        Ok(parts) => [&parts[0], &parts[1]].map(|part| {
+            let answer = part.result.answer().map_or_else(dash, Answer::summary);
+            (
+                answer,
+                runner::format_duration(part.duration),
+                part.allocation_summary(),
+            )
+        }),
+        Err(_) => [(dash(), dash(), dash()), (dash(), dash(), dash())],
+    };
+
+    [
+        outcome.name.clone(),
+        parse,
+        answer1,
+        time1,
+        memory1,
+        answer2,
+        time2,
+        memory2,
+        status,
+    ]
+}
@@ -0,0 +1,253 @@
+use std::hint::black_box;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::aoc::{self, Part};
+use crate::input;
+use crate::runner::{self, DaySelection, PartResult};
+
+/// How many times to run each step, not counting warmup runs.
+pub struct BenchOptions {
+    pub warmup: usize,
+    pub runs: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part(Part),
+}
+
+impl FromStr for Step {
+    type Err = anyhow::Error;
+
+    fn from_str(step: &str) -> anyhow::Result<Self> {
+        match step {
+            "parse" => Ok(Self::Parse),
+            part => part.trim_start_matches("part").parse().map(Self::Part),
+        }
+    }
+}
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse => write!(f, "parse"),
+            Self::Part(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    fn from_times(mut times: Vec<Duration>) -> Self {
+        times.sort();
+
+        let runs = times.len();
+        let median = if runs % 2 == 0 {
+            (times[runs / 2 - 1] + times[runs / 2]) / 2
+        } else {
+            times[runs / 2]
+        };
+
+        let mean = times.iter().map(Duration::as_secs_f64).sum::<f64>() / runs as f64;
+        let variance = times
+            .iter()
+            .map(|time| (time.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+
+        Self {
+            runs,
+            min: times[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+pub struct StepBench {
+    pub step: Step,
+    pub stats: Result<Stats, PartResult>,
+}
+
+pub struct DayBench {
+    pub day: usize,
+    pub input_hash: Option<String>,
+    pub steps: anyhow::Result<Vec<StepBench>>,
+}
+
+/// Runs a step a few times to warm up, then times it on every run after.
+/// The first failure or panic stops the step and is returned instead.
+fn measure<T>(
+    options: &BenchOptions,
+    step: impl Fn() -> anyhow::Result<T>,
+) -> Result<Stats, PartResult> {
+    PartResult::catch(|| {
+        for _ in 0..options.warmup {
+            black_box(step()?);
+        }
+
+        let mut times = Vec::with_capacity(options.runs);
+        for _ in 0..options.runs.max(1) {
+            let start = Instant::now();
+            black_box(step()?);
+            times.push(start.elapsed());
+        }
+
+        Ok(Stats::from_times(times))
+    })
+}
+
+pub async fn bench_day(year: i32, day: usize, options: &BenchOptions) -> DayBench {
+    let mut input_hash = None;
+    let steps = async {
+        let day_impl = aoc::get_day(year, day)?;
+        let input_for_day = input::get_input(year, day).await?;
+        input_hash = Some(input::hash(&input_for_day));
+
+        let (prepared, parse) = runner::prepare(day_impl.as_ref(), &input_for_day)?;
+
+        // Parsing only gets a step of its own for days that do it up front,
+        // the rest parse inside each part
+        let mut steps = Vec::new();
+        if parse.is_some() {
+            steps.push(StepBench {
+                step: Step::Parse,
+                stats: measure(options, || day_impl.prepare(&input_for_day)),
+            });
+        }
+
+        for part in Part::BOTH {
+            steps.push(StepBench {
+                step: Step::Part(part),
+                stats: measure(options, || prepared.solve(part)),
+            });
+        }
+
+        Ok(steps)
+    }
+    .await;
+
+    DayBench {
+        day,
+        input_hash,
+        steps,
+    }
+}
+
+pub async fn bench(year: i32, selection: &DaySelection, options: &BenchOptions) -> Vec<DayBench> {
+    let mut benches = Vec::new();
+    for &day in &selection.0 {
+        eprintln!("Benchmarking day {}...", day);
+        benches.push(bench_day(year, day, options).await);
+    }
+
+    benches
+}
+
+pub fn print_benches(benches: &[DayBench]) {
+    let header = [
+        "Day", "Step", "Runs", "Min", "Median", "Mean", "Stddev", "Status",
+    ]
+    .map(String::from);
+
+    let mut rows = Vec::new();
+    for bench in benches {
+        match &bench.steps {
+            Err(error) => rows.push(problem_row(bench.day, "-", format!("error: {}", error))),
+            Ok(steps) => rows.extend(steps.iter().map(|step| step_row(bench.day, step))),
+        }
+    }
+
+    runner::print_table(header, &rows);
+}
+
+fn step_row(day: usize, step: &StepBench) -> [String; 8] {
+    match &step.stats {
+        Ok(stats) => [
+            day.to_string(),
+            step.step.to_string(),
+            stats.runs.to_string(),
+            runner::format_duration(stats.min),
+            runner::format_duration(stats.median),
+            runner::format_duration(stats.mean),
+            runner::format_duration(stats.stddev),
+            "ok".to_owned(),
+        ],
+        Err(result) => problem_row(day, &step.step.to_string(), result.to_string()),
+    }
+}
+
+fn problem_row(day: usize, step: &str, problem: String) -> [String; 8] {
+    let none = || "-".to_owned();
+
+    [
+        day.to_string(),
+        step.to_owned(),
+        none(),
+        none(),
+        none(),
+        none(),
+        none(),
+        problem,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    fn millis(times: &[u64]) -> Vec<Duration> {
+        times.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    fn assert_close(actual: Duration, expected_millis: f64) {
+        let difference = actual.as_secs_f64() * 1000.0 - expected_millis;
+        assert!(
+            difference.abs() < 1e-6,
+            "{:?} isn't {}ms",
+            actual,
+            expected_millis
+        );
+    }
+
+    #[test]
+    fn averages_the_middle_runs_of_an_even_count() {
+        let stats = Stats::from_times(millis(&[4, 1, 3, 2]));
+
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_close(stats.mean, 2.5);
+        assert_close(stats.stddev, 1.25_f64.sqrt());
+    }
+
+    #[test]
+    fn takes_the_middle_run_of_an_odd_count() {
+        let stats = Stats::from_times(millis(&[5, 1, 3]));
+
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_close(stats.mean, 3.0);
+        assert_close(stats.stddev, (8.0_f64 / 3.0).sqrt());
+    }
+
+    #[test]
+    fn has_no_spread_for_identical_runs() {
+        let stats = Stats::from_times(millis(&[7, 7]));
+
+        assert_eq!(stats.median, Duration::from_millis(7));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+}
@@ -0,0 +1,382 @@
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, read_dir, read_to_string, remove_file, write};
+use std::path::Path;
+
+use anyhow::Context;
+use chrono::Utc;
+
+use crate::{input, memory, runner};
+
+/// The SHA-256 of every input as it was fetched, so inputs that have been
+/// truncated or edited since can be caught before they're run.
+///
+/// Entries are stored one per line as `day`, `sha256`, `bytes` and the
+/// time it was fetched, separated by tabs.
+pub struct Manifest {
+    path: String,
+    entries: BTreeMap<usize, Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub day: usize,
+    pub sha256: String,
+    pub bytes: usize,
+    pub fetched: String,
+}
+
+/// How a cached input compares to the manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Modified,
+    /// Cached before there was a manifest, so there's nothing to check.
+    Untracked,
+    Missing,
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ok => write!(f, "ok"),
+            Self::Modified => write!(f, "MODIFIED"),
+            Self::Untracked => write!(f, "no checksum"),
+            Self::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+impl Manifest {
+    pub fn load(year: i32) -> anyhow::Result<Self> {
+        let path = format!("{}/manifest.tsv", input::cache_dir(year));
+        if !Path::new(&path).exists() {
+            return Ok(Self {
+                path,
+                entries: BTreeMap::new(),
+            });
+        }
+
+        let entries = read_to_string(&path)
+            .context("Failed to read input manifest")?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Entry::parse(line).map(|entry| (entry.day, entry)))
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self { path, entries })
+    }
+
+    fn save(&self) -> anyhow::Result<()> {
+        if let Some(folder) = Path::new(&self.path).parent() {
+            create_dir_all(folder).context("Failed to create input folder")?;
+        }
+
+        let contents: String = self
+            .entries
+            .values()
+            .map(|entry| format!("{}\n", entry))
+            .collect();
+        write(&self.path, contents).context("Failed to write input manifest")
+    }
+
+    /// Remembers the checksum of a freshly fetched input.
+    pub fn record(&mut self, day: usize, input: &str) -> anyhow::Result<()> {
+        self.entries.insert(
+            day,
+            Entry {
+                day,
+                sha256: input::hash(input),
+                bytes: input.len(),
+                fetched: Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            },
+        );
+
+        self.save()
+    }
+
+    pub fn remove(&mut self, day: usize) -> anyhow::Result<()> {
+        if self.entries.remove(&day).is_some() {
+            self.save()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, day: usize) -> Option<&Entry> {
+        self.entries.get(&day)
+    }
+
+    pub fn status(&self, day: usize, input: Option<&str>) -> Status {
+        match (self.get(day), input) {
+            (Some(_), None) => Status::Missing,
+            (Some(entry), Some(input)) if entry.sha256 != input::hash(input) => Status::Modified,
+            (Some(_), Some(_)) => Status::Ok,
+            (None, _) => Status::Untracked,
+        }
+    }
+
+    /// Fails if a cached input no longer matches what was fetched.
+    pub fn check(&self, day: usize, input: &str) -> anyhow::Result<()> {
+        if self.status(day, Some(input)) == Status::Modified {
+            anyhow::bail!(
+                "The cached input for day {} doesn't match the one that was fetched, it may \
+                 have been truncated or edited. Run `cache refetch --day {}` to fetch it again",
+                day,
+                day
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Every day with a cached input or a manifest entry.
+fn cached_days(year: i32, manifest: &Manifest) -> anyhow::Result<Vec<usize>> {
+    let mut days: Vec<usize> = manifest.entries.keys().copied().collect();
+
+    let folder = input::cache_dir(year);
+    if Path::new(&folder).exists() {
+        for entry in read_dir(&folder).context("Failed to read input folder")? {
+            let name = entry.context("Failed to read input folder")?.file_name();
+            let day = name
+                .to_str()
+                .and_then(|name| name.strip_suffix(".txt"))
+                .and_then(|day| day.parse::<usize>().ok());
+            days.extend(day);
+        }
+    }
+
+    days.sort_unstable();
+    days.dedup();
+
+    Ok(days)
+}
+
+fn read_cached(year: i32, day: usize) -> Option<String> {
+    read_to_string(input::input_path(year, day)).ok()
+}
+
+pub fn list(year: i32) -> anyhow::Result<()> {
+    let manifest = Manifest::load(year)?;
+    let days = cached_days(year, &manifest)?;
+    if days.is_empty() {
+        println!("No inputs cached for {}", year);
+        return Ok(());
+    }
+
+    let header = ["Day", "Size", "Fetched", "Checksum"].map(String::from);
+    let rows: Vec<[String; 4]> = days
+        .into_iter()
+        .map(|day| {
+            let input = read_cached(year, day);
+            let size = input.as_ref().map(|input| input.len() as u64);
+
+            [
+                day.to_string(),
+                size.map_or_else(|| "-".to_owned(), memory::format_bytes),
+                manifest
+                    .get(day)
+                    .map_or_else(|| "-".to_owned(), |entry| entry.fetched.clone()),
+                manifest.status(day, input.as_deref()).to_string(),
+            ]
+        })
+        .collect();
+
+    runner::print_table(header, &rows);
+
+    Ok(())
+}
+
+/// Removes the cached inputs and examples for a day, or for every day.
+/// Submissions and benchmark history are kept.
+pub fn clear(year: i32, day: Option<usize>) -> anyhow::Result<()> {
+    let mut manifest = Manifest::load(year)?;
+    let folder = input::cache_dir(year);
+    if !Path::new(&folder).exists() {
+        println!("No inputs cached for {}", year);
+        return Ok(());
+    }
+
+    let mut removed = 0;
+    for entry in read_dir(&folder).context("Failed to read input folder")? {
+        let path = entry.context("Failed to read input folder")?.path();
+        let file_day = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(".txt"))
+            .and_then(|name| name.split('.').next())
+            .and_then(|file_day| file_day.parse::<usize>().ok());
+
+        match file_day {
+            Some(file_day) if day.is_none() || day == Some(file_day) => {
+                remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+                removed += 1;
+            }
+            _ => {}
+        }
+    }
+
+    match day {
+        Some(day) => manifest.remove(day)?,
+        None => {
+            manifest.entries.clear();
+            manifest.save()?;
+        }
+    }
+
+    println!("Removed {} cached file(s)", removed);
+
+    Ok(())
+}
+
+/// Fetches inputs again, replacing what's cached along with their
+/// checksums.
+pub async fn refetch(year: i32, day: Option<usize>) -> anyhow::Result<()> {
+    let days = match day {
+        Some(day) => vec![day],
+        None => cached_days(year, &Manifest::load(year)?)?,
+    };
+    if days.is_empty() {
+        anyhow::bail!("No inputs cached for {} to refetch", year);
+    }
+
+    for day in days {
+        let input = input::fetch_input(year, day).await?;
+        println!(
+            "Fetched day {} ({})",
+            day,
+            memory::format_bytes(input.len() as u64)
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks every cached input against its checksum, failing if any were
+/// changed or have gone missing.
+pub fn verify(year: i32) -> anyhow::Result<()> {
+    let manifest = Manifest::load(year)?;
+    let days = cached_days(year, &manifest)?;
+    if days.is_empty() {
+        println!("No inputs cached for {}", year);
+        return Ok(());
+    }
+
+    let mut failures = 0;
+    for day in days {
+        let input = read_cached(year, day);
+        let status = manifest.status(day, input.as_deref());
+        match (status, manifest.get(day), input) {
+            // A change in size usually means the input was cut short
+            (Status::Modified, Some(entry), Some(input)) if input.len() != entry.bytes => println!(
+                "Day {}: {} ({} when fetched, {} now)",
+                day,
+                status,
+                memory::format_bytes(entry.bytes as u64),
+                memory::format_bytes(input.len() as u64)
+            ),
+            _ => println!("Day {}: {}", day, status),
+        }
+
+        if matches!(status, Status::Modified | Status::Missing) {
+            failures += 1;
+        }
+    }
+
+    if failures > 0 {
+        anyhow::bail!(
+            "{} input(s) don't match the manifest, run `cache refetch` to fetch them again",
+            failures
+        );
+    }
+
+    Ok(())
+}
+
+impl Entry {
+    fn parse(line: &str) -> anyhow::Result<Self> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 4 {
+            anyhow::bail!("Input manifest entry should have 4 fields: {}", line);
+        }
+
+        Ok(Self {
+            day: fields[0].parse().context("Invalid manifest day")?,
+            sha256: fields[1].to_owned(),
+            bytes: fields[2].parse().context("Invalid manifest size")?,
+            fetched: fields[3].to_owned(),
+        })
+    }
+}
+
+impl std::fmt::Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}",
+            self.day, self.sha256, self.bytes, self.fetched
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{Entry, Manifest, Status};
+    use crate::input;
+
+    fn manifest(inputs: &[(usize, &str)]) -> Manifest {
+        let entries = inputs
+            .iter()
+            .map(|&(day, input)| {
+                let entry = Entry {
+                    day,
+                    sha256: input::hash(input),
+                    bytes: input.len(),
+                    fetched: "2021-12-01T05:00:00Z".to_owned(),
+                };
+
+                (day, entry)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        Manifest {
+            path: String::new(),
+            entries,
+        }
+    }
+
+    #[test]
+    fn compares_inputs_to_their_checksums() {
+        let manifest = manifest(&[(1, "199\n200\n208\n")]);
+
+        assert_eq!(manifest.status(1, Some("199\n200\n208\n")), Status::Ok);
+        assert_eq!(manifest.status(1, Some("199\n200\n")), Status::Modified);
+        assert_eq!(manifest.status(1, None), Status::Missing);
+        assert_eq!(manifest.status(2, Some("forward 5\n")), Status::Untracked);
+        assert_eq!(manifest.status(2, None), Status::Untracked);
+    }
+
+    #[test]
+    fn only_fails_the_check_for_modified_inputs() {
+        let manifest = manifest(&[(1, "199\n200\n208\n")]);
+
+        assert!(manifest.check(1, "199\n200\n208\n").is_ok());
+        assert!(manifest.check(1, "199\n200\n").is_err());
+        assert!(manifest.check(2, "forward 5\n").is_ok());
+    }
+
+    #[test]
+    fn manifest_entries_round_trip() {
+        let entry = Entry {
+            day: 6,
+            sha256: "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08".to_owned(),
+            bytes: 600,
+            fetched: "2021-12-06T05:00:02Z".to_owned(),
+        };
+
+        assert_eq!(Entry::parse(&entry.to_string()).unwrap(), entry);
+    }
+}
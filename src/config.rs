@@ -0,0 +1,342 @@
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::output::OutputFormat;
+use crate::sandbox::Limits;
+
+const PROJECT_CONFIG_PATH: &str = "aoc.toml";
+const DEFAULT_CACHE_DIR: &str = "input";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Settings that can come from `aoc.toml`, the XDG config file, the
+/// environment or the command line, each overriding the ones before it:
+///
+/// ```toml
+/// session = "53616c74..."
+/// year = 2021
+/// cache-dir = "input"
+/// base-url = "https://adventofcode.com"
+/// format = "json"
+/// timeout = 10.0
+/// max-memory = 1024
+///
+/// # Other accounts, picked with `--profile` or `profile = "alice"`
+/// [profiles.alice]
+/// session = "53616c74..."
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    pub session: Option<String>,
+    pub profile: Option<String>,
+    pub profiles: BTreeMap<String, Profile>,
+    pub year: Option<i32>,
+    pub cache_dir: Option<String>,
+    pub base_url: Option<String>,
+    pub format: Option<OutputFormat>,
+    /// In seconds.
+    pub timeout: Option<f64>,
+    /// In megabytes.
+    pub max_memory: Option<u64>,
+}
+
+/// Another account, with its own session and its own corner of the cache.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Profile {
+    pub session: Option<String>,
+}
+
+impl Config {
+    /// Reads every layer up to the environment, leaving the command line
+    /// to be merged on top.
+    pub fn load() -> anyhow::Result<Self> {
+        let mut config = Self::default();
+        for path in Self::files() {
+            if path.exists() {
+                config = config.merge(Self::read(&path)?);
+            }
+        }
+
+        Ok(config.merge(Self::from_env()?))
+    }
+
+    /// The config files in the order they're layered.
+    pub fn files() -> Vec<PathBuf> {
+        let mut files = vec![PathBuf::from(PROJECT_CONFIG_PATH)];
+
+        let config_home = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+        if let Some(config_home) = config_home {
+            files.push(config_home.join("aoc").join("config.toml"));
+        }
+
+        files
+    }
+
+    fn read(path: &Path) -> anyhow::Result<Self> {
+        let contents = read_to_string(path)
+            .with_context(|| format!("Failed to read config from {}", path.display()))?;
+
+        toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse config from {}", path.display()))
+    }
+
+    fn from_env() -> anyhow::Result<Self> {
+        Ok(Self {
+            session: std::env::var("SESSION").ok(),
+            profile: std::env::var("AOC_PROFILE").ok(),
+            profiles: BTreeMap::new(),
+            year: env_value("AOC_YEAR")?,
+            cache_dir: std::env::var("AOC_CACHE_DIR").ok(),
+            base_url: std::env::var("AOC_BASE_URL").ok(),
+            format: env_value("AOC_FORMAT")?,
+            timeout: env_value("AOC_TIMEOUT")?,
+            max_memory: env_value("AOC_MAX_MEMORY")?,
+        })
+    }
+
+    /// Layers `other` on top, keeping our values only where it has none.
+    pub fn merge(self, other: Self) -> Self {
+        let mut profiles = self.profiles;
+        profiles.extend(other.profiles);
+
+        Self {
+            session: other.session.or(self.session),
+            profile: other.profile.or(self.profile),
+            profiles,
+            year: other.year.or(self.year),
+            cache_dir: other.cache_dir.or(self.cache_dir),
+            base_url: other.base_url.or(self.base_url),
+            format: other.format.or(self.format),
+            timeout: other.timeout.or(self.timeout),
+            max_memory: other.max_memory.or(self.max_memory),
+        }
+    }
+
+    /// Fails if the selected profile isn't one we know about, before its
+    /// name ends up in any paths.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if let Some(name) = &self.profile {
+            if !self.profiles.contains_key(name) {
+                anyhow::bail!(
+                    "Unknown profile {}, add a [profiles.{}] table to the config",
+                    name,
+                    name
+                );
+            }
+
+            if name.is_empty() || name.contains(['/', '\\', '.']) {
+                anyhow::bail!("Invalid profile name {:?}", name);
+            }
+        }
+
+        if let Some(timeout) = self.timeout {
+            if timeout <= 0.0 || Duration::try_from_secs_f64(timeout).is_err() {
+                anyhow::bail!(
+                    "Invalid timeout {}, it must be a positive number of seconds",
+                    timeout
+                );
+            }
+        }
+
+        if let Some(max_memory) = self.max_memory {
+            if max_memory == 0 || megabytes_to_bytes(max_memory).is_none() {
+                anyhow::bail!("Invalid max-memory {} MB", max_memory);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The session token for the selected profile, or the default one.
+    pub fn session(&self) -> anyhow::Result<&str> {
+        match &self.profile {
+            Some(name) => self
+                .profiles
+                .get(name)
+                .and_then(|profile| profile.session.as_deref())
+                .with_context(|| format!("No session set for profile {}", name)),
+            None => self
+                .session
+                .as_deref()
+                .context("No session set, add SESSION to the environment or `session` to aoc.toml"),
+        }
+    }
+
+    /// Where the cache is kept, with each profile in its own folder.
+    pub fn cache_dir(&self) -> String {
+        let root = self.cache_dir.as_deref().unwrap_or(DEFAULT_CACHE_DIR);
+
+        match &self.profile {
+            Some(profile) => format!("{}/{}", root, profile),
+            None => root.to_owned(),
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        self.base_url
+            .as_deref()
+            .unwrap_or(DEFAULT_BASE_URL)
+            .trim_end_matches('/')
+    }
+
+    pub fn limits(&self) -> Limits {
+        Limits {
+            timeout: self
+                .timeout
+                .and_then(|timeout| Duration::try_from_secs_f64(timeout).ok()),
+            max_memory: self.max_memory.and_then(megabytes_to_bytes),
+        }
+    }
+
+    /// Every setting with its resolved value, hiding the session tokens.
+    pub fn show(&self) -> Vec<(String, String)> {
+        let or_unset = |value: Option<String>| value.unwrap_or_else(|| "(not set)".to_owned());
+        let format = self.format.map(|format| format.to_string());
+        let timeout = self.timeout.map(|timeout| format!("{}s", timeout));
+        let max_memory = self
+            .max_memory
+            .map(|max_memory| format!("{} MB", max_memory));
+
+        let mut settings = vec![
+            ("session", or_unset(self.session.as_deref().map(mask))),
+            ("profile", or_unset(self.profile.clone())),
+            ("year", or_unset(self.year.map(|year| year.to_string()))),
+            ("cache-dir", self.cache_dir()),
+            ("base-url", self.base_url().to_owned()),
+            ("format", or_unset(format)),
+            ("timeout", or_unset(timeout)),
+            ("max-memory", or_unset(max_memory)),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_owned(), value))
+        .collect::<Vec<_>>();
+
+        for (name, profile) in &self.profiles {
+            settings.push((
+                format!("profiles.{}.session", name),
+                or_unset(profile.session.as_deref().map(mask)),
+            ));
+        }
+
+        settings
+    }
+}
+
+/// Makes the config available everywhere, once the command line has been
+/// merged in.
+pub fn init(config: Config) {
+    CONFIG.set(config).ok();
+}
+
+/// The config set by [`init`], or just the defaults before then.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+fn env_value<T>(name: &str) -> anyhow::Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    match std::env::var(name) {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|error| anyhow::anyhow!("Invalid {}: {}", name, error)),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Hides all but the end of a secret, enough to tell tokens apart.
+fn mask(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    let visible: String = if chars.len() > 8 {
+        chars[chars.len() - 4..].iter().collect()
+    } else {
+        String::new()
+    };
+
+    format!("********{}", visible)
+}
+
+pub fn print_config(config: &Config) {
+    for path in Config::files() {
+        let status = if path.exists() { "loaded" } else { "not found" };
+        println!("# {} ({})", path.display(), status);
+    }
+
+    for (key, value) in config.show() {
+        println!("{} = {}", key, value);
+    }
+}
+
+fn megabytes_to_bytes(megabytes: u64) -> Option<u64> {
+    megabytes.checked_mul(1024 * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mask, Config};
+
+    #[test]
+    fn later_layers_override_earlier_ones() {
+        let project = Config {
+            year: Some(2020),
+            cache_dir: Some("cache".to_owned()),
+            ..Config::default()
+        };
+        let env = Config {
+            year: Some(2021),
+            ..Config::default()
+        };
+
+        let config = project.merge(env);
+        assert_eq!(config.year, Some(2021));
+        assert_eq!(config.cache_dir(), "cache");
+        assert_eq!(config.base_url(), "https://adventofcode.com");
+    }
+
+    #[test]
+    fn masks_secrets() {
+        assert_eq!(mask("53616c7465645f5f1234"), "********1234");
+        assert_eq!(mask("short"), "********");
+    }
+
+    #[test]
+    fn rejects_unusable_limits() {
+        for timeout in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let config = Config {
+                timeout: Some(timeout),
+                ..Config::default()
+            };
+            assert!(config.validate().is_err(), "timeout {}", timeout);
+        }
+
+        let config = Config {
+            max_memory: Some(u64::MAX),
+            ..Config::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = Config {
+            timeout: Some(2.5),
+            max_memory: Some(512),
+            ..Config::default()
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(config.limits().max_memory, Some(512 * 1024 * 1024));
+    }
+}
@@ -1,13 +0,0 @@
-use crate::aoc::Aoc;
-
-pub struct Day19;
-
-impl Aoc for Day19 {
-    fn part1(&self, _input: &str) -> anyhow::Result<usize> {
-        unimplemented!()
-    }
-
-    fn part2(&self, _input: &str) -> anyhow::Result<usize> {
-        unimplemented!()
-    }
-}
@@ -1,13 +0,0 @@
-use crate::aoc::Aoc;
-
-pub struct Day23;
-
-impl Aoc for Day23 {
-    fn part1(&self, _input: &str) -> anyhow::Result<usize> {
-        unimplemented!()
-    }
-
-    fn part2(&self, _input: &str) -> anyhow::Result<usize> {
-        unimplemented!()
-    }
-}
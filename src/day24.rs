@@ -1,13 +0,0 @@
-use crate::aoc::Aoc;
-
-pub struct Day24;
-
-impl Aoc for Day24 {
-    fn part1(&self, _input: &str) -> anyhow::Result<usize> {
-        unimplemented!()
-    }
-
-    fn part2(&self, _input: &str) -> anyhow::Result<usize> {
-        unimplemented!()
-    }
-}
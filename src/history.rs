@@ -0,0 +1,355 @@
+use std::fs::{create_dir_all, read_to_string, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::Utc;
+
+use crate::bench::{DayBench, Stats, Step};
+use crate::input;
+use crate::runner;
+
+/// Every benchmark run for a year, so runs from different commits can be
+/// compared.
+///
+/// Entries are stored one per line as `time`, `day`, `step`, `commit`,
+/// `input hash`, `runs` and then the min, median, mean and standard
+/// deviation in nanoseconds, separated by tabs.
+pub struct BenchHistory {
+    path: String,
+    entries: Vec<Entry>,
+}
+
+pub struct Entry {
+    pub time: String,
+    pub day: usize,
+    pub step: Step,
+    pub commit: String,
+    pub input_hash: String,
+    pub stats: Stats,
+}
+
+/// How a step's median time changed since its baseline.
+pub struct Comparison {
+    pub day: usize,
+    pub step: Step,
+    pub baseline: Duration,
+    pub current: Duration,
+    pub regressed: bool,
+}
+
+impl Comparison {
+    /// How much slower the step got, as a percentage of the baseline.
+    pub fn change_percent(&self) -> f64 {
+        let baseline = self.baseline.as_secs_f64();
+        if baseline == 0.0 {
+            return 0.0;
+        }
+
+        (self.current.as_secs_f64() - baseline) / baseline * 100.0
+    }
+}
+
+impl BenchHistory {
+    pub fn load(year: i32) -> anyhow::Result<Self> {
+        let path = format!("{}/bench-history.tsv", input::cache_dir(year));
+        if !Path::new(&path).exists() {
+            return Ok(Self {
+                path,
+                entries: vec![],
+            });
+        }
+
+        let entries = read_to_string(&path)
+            .context("Failed to read benchmark history")?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(Entry::parse)
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self { path, entries })
+    }
+
+    /// Saves every step that finished from a day's benchmark.
+    pub fn record(&mut self, commit: &str, bench: &DayBench) -> anyhow::Result<()> {
+        let (input_hash, steps) = match (&bench.input_hash, &bench.steps) {
+            (Some(input_hash), Ok(steps)) => (input_hash, steps),
+            _ => return Ok(()),
+        };
+
+        let time = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let new_entries: Vec<Entry> = steps
+            .iter()
+            .filter_map(|step| {
+                Some(Entry {
+                    time: time.clone(),
+                    day: bench.day,
+                    step: step.step,
+                    commit: commit.to_owned(),
+                    input_hash: input_hash.clone(),
+                    stats: *step.stats.as_ref().ok()?,
+                })
+            })
+            .collect();
+        if new_entries.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(folder) = Path::new(&self.path).parent() {
+            create_dir_all(folder).context("Failed to create input folder")?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .context("Failed to open benchmark history")?;
+        for entry in &new_entries {
+            writeln!(file, "{}", entry).context("Failed to write to benchmark history")?;
+        }
+
+        self.entries.extend(new_entries);
+
+        Ok(())
+    }
+
+    /// The latest run of a step on the same input from another commit.
+    pub fn baseline(
+        &self,
+        day: usize,
+        step: Step,
+        input_hash: &str,
+        commit: &str,
+    ) -> Option<&Entry> {
+        self.entries.iter().rev().find(|entry| {
+            entry.day == day
+                && entry.step == step
+                && entry.input_hash == input_hash
+                && entry.commit != commit
+        })
+    }
+
+    /// Compares each finished step against its baseline, flagging those
+    /// whose median got slower by more than `threshold` percent.
+    pub fn compare(&self, commit: &str, benches: &[DayBench], threshold: f64) -> Vec<Comparison> {
+        let mut comparisons = Vec::new();
+        for bench in benches {
+            let (input_hash, steps) = match (&bench.input_hash, &bench.steps) {
+                (Some(input_hash), Ok(steps)) => (input_hash, steps),
+                _ => continue,
+            };
+
+            for step in steps {
+                let stats = match &step.stats {
+                    Ok(stats) => stats,
+                    Err(_) => continue,
+                };
+
+                if let Some(baseline) = self.baseline(bench.day, step.step, input_hash, commit) {
+                    let mut comparison = Comparison {
+                        day: bench.day,
+                        step: step.step,
+                        baseline: baseline.stats.median,
+                        current: stats.median,
+                        regressed: false,
+                    };
+                    comparison.regressed = comparison.change_percent() > threshold;
+
+                    comparisons.push(comparison);
+                }
+            }
+        }
+
+        comparisons
+    }
+}
+
+/// The commit being benchmarked, marked dirty if there are uncommitted
+/// changes since they may be what changed the timing.
+pub fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    };
+
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit)
+            if git(&["status", "--porcelain"]).is_some_and(|changes| !changes.is_empty()) =>
+        {
+            format!("{}-dirty", commit)
+        }
+        Some(commit) => commit,
+        None => "unknown".to_owned(),
+    }
+}
+
+pub fn print_comparisons(comparisons: &[Comparison]) {
+    let header = ["Day", "Step", "Baseline", "Now", "Change", "Status"].map(String::from);
+    let rows: Vec<[String; 6]> = comparisons
+        .iter()
+        .map(|comparison| {
+            [
+                comparison.day.to_string(),
+                comparison.step.to_string(),
+                runner::format_duration(comparison.baseline),
+                runner::format_duration(comparison.current),
+                format!("{:+.1}%", comparison.change_percent()),
+                if comparison.regressed {
+                    "SLOWER".to_owned()
+                } else {
+                    "ok".to_owned()
+                },
+            ]
+        })
+        .collect();
+
+    runner::print_table(header, &rows);
+}
+
+impl Entry {
+    fn parse(line: &str) -> anyhow::Result<Self> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() != 10 {
+            anyhow::bail!("Benchmark history entry should have 10 fields: {}", line);
+        }
+
+        let nanos = |field: &str| -> anyhow::Result<Duration> {
+            let nanos = field.parse().context("Invalid benchmark time")?;
+
+            Ok(Duration::from_nanos(nanos))
+        };
+
+        Ok(Self {
+            time: fields[0].to_owned(),
+            day: fields[1].parse().context("Invalid benchmark day")?,
+            step: fields[2].parse()?,
+            commit: fields[3].to_owned(),
+            input_hash: fields[4].to_owned(),
+            stats: Stats {
+                runs: fields[5].parse().context("Invalid benchmark run count")?,
+                min: nanos(fields[6])?,
+                median: nanos(fields[7])?,
+                mean: nanos(fields[8])?,
+                stddev: nanos(fields[9])?,
+            },
+        })
+    }
+}
+
+impl std::fmt::Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.time,
+            self.day,
+            self.step,
+            self.commit,
+            self.input_hash,
+            self.stats.runs,
+            self.stats.min.as_nanos(),
+            self.stats.median.as_nanos(),
+            self.stats.mean.as_nanos(),
+            self.stats.stddev.as_nanos()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{BenchHistory, Entry};
+    use crate::aoc::Part;
+    use crate::bench::{DayBench, Stats, Step, StepBench};
+
+    fn stats(median_millis: u64) -> Stats {
+        let median = Duration::from_millis(median_millis);
+
+        Stats {
+            runs: 10,
+            min: median,
+            median,
+            mean: median,
+            stddev: Duration::ZERO,
+        }
+    }
+
+    fn entry(commit: &str, input_hash: &str, median_millis: u64) -> Entry {
+        Entry {
+            time: "2021-12-01T05:00:00Z".to_owned(),
+            day: 1,
+            step: Step::Part(Part::One),
+            commit: commit.to_owned(),
+            input_hash: input_hash.to_owned(),
+            stats: stats(median_millis),
+        }
+    }
+
+    fn bench(input_hash: &str, median_millis: u64) -> DayBench {
+        DayBench {
+            day: 1,
+            input_hash: Some(input_hash.to_owned()),
+            steps: Ok(vec![StepBench {
+                step: Step::Part(Part::One),
+                stats: Ok(stats(median_millis)),
+            }]),
+        }
+    }
+
+    #[test]
+    fn entries_round_trip() {
+        let line =
+            "2021-12-01T05:00:00Z\t3\tpart 2\tabc1234-dirty\tdeadbeef\t25\t1000\t1500\t1600\t120";
+        let entry = Entry::parse(line).unwrap();
+
+        assert_eq!(entry.day, 3);
+        assert_eq!(entry.step, Step::Part(Part::Two));
+        assert_eq!(entry.stats.median, Duration::from_nanos(1500));
+        assert_eq!(entry.to_string(), line);
+
+        assert!(Entry::parse("2021-12-01T05:00:00Z\t3\tparse").is_err());
+    }
+
+    #[test]
+    fn flags_regressions_past_the_threshold() {
+        let history = BenchHistory {
+            path: String::new(),
+            entries: vec![entry("old", "input", 100)],
+        };
+
+        let comparisons = history.compare("new", &[bench("input", 109)], 10.0);
+        assert_eq!(comparisons.len(), 1);
+        assert!(!comparisons[0].regressed);
+
+        let comparisons = history.compare("new", &[bench("input", 111)], 10.0);
+        assert!(comparisons[0].regressed);
+    }
+
+    #[test]
+    fn compares_against_the_latest_run_from_another_commit_on_the_same_input() {
+        let history = BenchHistory {
+            path: String::new(),
+            entries: vec![
+                entry("older", "input", 100),
+                entry("old", "input", 200),
+                entry("old", "other input", 50),
+                entry("new", "input", 300),
+            ],
+        };
+
+        let comparisons = history.compare("new", &[bench("input", 210)], 10.0);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].baseline, Duration::from_millis(200));
+        assert!(!comparisons[0].regressed);
+
+        assert!(history
+            .compare("new", &[bench("unseen input", 210)], 10.0)
+            .is_empty());
+    }
+}
@@ -1,35 +1,342 @@
-use std::fs::{create_dir_all, read_to_string, try_exists, write};
+use std::collections::hash_map::RandomState;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::hash::{BuildHasher, Hasher};
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
 
 use anyhow::Context;
+use sha2::{Digest, Sha256};
 
-pub async fn get_input(day: usize) -> anyhow::Result<String> {
-    let path = format!("input/{}.txt", day);
-    if matches!(try_exists(&path), Ok(true)) {
-        return read_to_string(&path).context("Failed to read input from file");
+use crate::cache::Manifest;
+use crate::config;
+
+/// Where a year's inputs, examples and submissions are cached, kept apart
+/// for each profile.
+pub fn cache_dir(year: i32) -> String {
+    format!("{}/{}", config::get().cache_dir(), year)
+}
+
+pub fn input_path(year: i32, day: usize) -> String {
+    format!("{}/{}.txt", cache_dir(year), day)
+}
+
+/// Reads a day's input from the cache, checking it against the manifest,
+/// or fetches it if it isn't cached yet.
+pub async fn get_input(year: i32, day: usize) -> anyhow::Result<String> {
+    let path = input_path(year, day);
+    if Path::new(&path).exists() {
+        let input = read_to_string(&path).context("Failed to read input from file")?;
+        Manifest::load(year)?.check(day, &input)?;
+
+        return Ok(input);
     }
 
-    let input = retrieve_input(day).await?;
+    fetch_input(year, day).await
+}
+
+/// Fetches a day's input and caches it along with its checksum, replacing
+/// whatever was cached before.
+pub async fn fetch_input(year: i32, day: usize) -> anyhow::Result<String> {
+    let input = retrieve_input(year, day).await?;
 
-    create_dir_all("input").context("Failed to create input folder")?;
-    write(path, &input).context("Failed to save input locally")?;
+    create_dir_all(cache_dir(year)).context("Failed to create input folder")?;
+    write(input_path(year, day), &input).context("Failed to save input locally")?;
+    Manifest::load(year)?.record(day, &input)?;
 
     Ok(input)
 }
 
-async fn retrieve_input(day: usize) -> anyhow::Result<String> {
-    let session = std::env::var("SESSION").context("SESSION environment variable missing")?;
-    let url = format!("https://adventofcode.com/2021/day/{}/input", day);
+/// Reads an input from a file, or from stdin when the path is `-`, leaving
+/// the cache alone.
+pub fn read_input(path: &str) -> anyhow::Result<String> {
+    if path == "-" {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .context("Failed to read input from stdin")?;
+
+        return Ok(input);
+    }
+
+    read_to_string(path).with_context(|| format!("Failed to read input from {}", path))
+}
+
+/// Fetches a day's input just after it unlocks, retrying with some jitter
+/// until the server has it ready.
+pub async fn get_input_with_retries(year: i32, day: usize) -> anyhow::Result<String> {
+    const MAX_ATTEMPTS: u64 = 10;
+
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+
+        match get_input(year, day).await {
+            Err(error) if attempt < MAX_ATTEMPTS && is_retryable(&error) => {
+                let delay = Duration::from_millis(500 * attempt + jitter_millis());
+                eprintln!(
+                    "{:#}, retrying in {:.1} seconds...",
+                    error,
+                    delay.as_secs_f64()
+                );
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn is_retryable(error: &anyhow::Error) -> bool {
+    match error.downcast_ref::<FetchError>() {
+        Some(FetchError::NotUnlocked | FetchError::RateLimited | FetchError::EmptyResponse) => true,
+        Some(FetchError::Status(status)) => *status >= 500,
+        Some(FetchError::ExpiredSession) => false,
+        None => error.downcast_ref::<reqwest::Error>().is_some(),
+    }
+}
+
+/// Up to a second of jitter so retries don't line up with everyone else's,
+/// taken from the random keys std seeds its hash maps with.
+fn jitter_millis() -> u64 {
+    RandomState::new().build_hasher().finish() % 1000
+}
+
+/// A SHA-256 hash of an input, in hex, to tell inputs apart without
+/// storing them.
+pub fn hash(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn example_path(year: i32, day: usize, index: usize) -> String {
+    format!("{}/{}.example.{}.txt", cache_dir(year), day, index)
+}
+
+/// Gets the `index`th example (starting at 1) from a day's puzzle page,
+/// caching every example on the page the first time it's fetched.
+pub async fn get_example(year: i32, day: usize, index: usize) -> anyhow::Result<String> {
+    let path = example_path(year, day, index);
+    if Path::new(&path).exists() {
+        return read_to_string(&path).context("Failed to read example from file");
+    }
+
+    // Once the page's examples are cached, asking for one past the end
+    // doesn't need another trip to the server
+    let cached = (1..)
+        .take_while(|index| Path::new(&example_path(year, day, *index)).exists())
+        .count();
+    if cached > 0 {
+        return Err(no_example(day, index, cached));
+    }
+
+    let page = retrieve_puzzle_page(year, day).await?;
+    let examples = extract_code_blocks(&page);
+    if examples.is_empty() {
+        anyhow::bail!("No examples found on the page for day {}", day);
+    }
+
+    save_examples(year, day, &examples)?;
+
+    let count = examples.len();
+    index
+        .checked_sub(1)
+        .and_then(|index| examples.into_iter().nth(index))
+        .ok_or_else(|| no_example(day, index, count))
+}
+
+fn no_example(day: usize, index: usize, count: usize) -> anyhow::Error {
+    anyhow::anyhow!(
+        "Day {} has no example {}, pick one from 1 to {}",
+        day,
+        index,
+        count
+    )
+}
+
+/// Caches a day's examples as `N.example.K.txt`, numbered from 1.
+pub fn save_examples(year: i32, day: usize, examples: &[String]) -> anyhow::Result<()> {
+    create_dir_all(cache_dir(year)).context("Failed to create input folder")?;
+    for (index, example) in examples.iter().enumerate() {
+        write(example_path(year, day, index + 1), example)
+            .context("Failed to save example locally")?;
+    }
+
+    Ok(())
+}
+
+/// Reads the puzzle's title out of its `--- Day N: Title ---` heading.
+pub fn extract_title(html: &str) -> Option<String> {
+    let (_, heading) = html.split_once("<h2>")?;
+    let (heading, _) = heading.split_once("</h2>")?;
+    let (_, title) = heading
+        .trim_matches(|c| c == '-' || c == ' ')
+        .split_once(": ")?;
+
+    Some(decode_entities(title))
+}
+
+/// Pulls the contents of every `<pre><code>` block out of a puzzle page,
+/// with formatting tags like `<em>` removed and entities decoded.
+pub fn extract_code_blocks(html: &str) -> Vec<String> {
+    html.split("<pre><code>")
+        .skip(1)
+        .filter_map(|block| block.split_once("</code></pre>"))
+        .map(|(code, _rest)| decode_entities(&strip_tags(code)))
+        .collect()
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The Advent of Code server to talk to, which can be overridden with
+/// `base-url` in the config to point at a local mock server.
+pub fn base_url() -> String {
+    config::get().base_url().to_owned()
+}
+
+pub fn session_cookie() -> anyhow::Result<String> {
+    let session = config::get().session()?;
+
+    Ok(format!("session={}", session))
+}
+
+/// Why the Advent of Code server refused to give us a page.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FetchError {
+    ExpiredSession,
+    NotUnlocked,
+    RateLimited,
+    EmptyResponse,
+    Status(u16),
+}
+
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ExpiredSession => {
+                write!(f, "Not logged in, the SESSION cookie is missing or expired")
+            }
+            Self::NotUnlocked => write!(f, "This puzzle hasn't unlocked yet"),
+            Self::RateLimited => write!(f, "Rate limited by the server, try again later"),
+            Self::EmptyResponse => write!(f, "The server sent back an empty response"),
+            Self::Status(status) => write!(f, "The server responded with status {}", status),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+/// Catches the error pages the server sends instead of the content we
+/// asked for, so they never get cached.
+pub fn check_response(status: u16, body: &str) -> Result<(), FetchError> {
+    if body.contains("Please log in") || body.contains("Please login") {
+        Err(FetchError::ExpiredSession)
+    } else if body.contains("before it unlocks") || status == 404 {
+        Err(FetchError::NotUnlocked)
+    } else if status == 429 || body.contains("too many requests") {
+        Err(FetchError::RateLimited)
+    } else if status == 400 || status == 401 || status == 403 {
+        Err(FetchError::ExpiredSession)
+    } else if !(200..300).contains(&status) {
+        Err(FetchError::Status(status))
+    } else if body.trim().is_empty() {
+        Err(FetchError::EmptyResponse)
+    } else {
+        Ok(())
+    }
+}
+
+pub async fn send(request: reqwest::RequestBuilder) -> anyhow::Result<String> {
+    let response = request.send().await.context("Error reaching the server")?;
+    let status = response.status().as_u16();
+    let body = response.text().await.context("Failed to parse text")?;
+
+    check_response(status, &body)?;
+
+    Ok(body)
+}
+
+async fn retrieve_input(year: i32, day: usize) -> anyhow::Result<String> {
+    let url = format!("{}/{}/day/{}/input", base_url(), year, day);
 
     let client = reqwest::Client::new();
-    let request = client
-        .get(url)
-        .header("Cookie", format!("session={}", session));
-
-    request
-        .send()
-        .await
-        .context("Error getting input")?
-        .text()
-        .await
-        .context("Failed to parse text")
+    let request = client.get(url).header("Cookie", session_cookie()?);
+
+    send(request).await.context("Error getting input")
+}
+
+pub async fn retrieve_puzzle_page(year: i32, day: usize) -> anyhow::Result<String> {
+    let url = format!("{}/{}/day/{}", base_url(), year, day);
+
+    let client = reqwest::Client::new();
+    let request = client.get(url).header("Cookie", session_cookie()?);
+
+    send(request).await.context("Error getting puzzle page")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_response, extract_code_blocks, extract_title, FetchError};
+
+    #[test]
+    fn extracts_examples_from_puzzle_page() {
+        let page = include_str!("../fixtures/2021-day1.html");
+        let examples = extract_code_blocks(page);
+
+        assert_eq!(examples.len(), 2);
+        assert_eq!(
+            examples[0],
+            "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n"
+        );
+        assert!(examples[1].starts_with("199 (N/A - no previous measurement)\n200 (increased)\n"));
+        assert!(examples[1].ends_with("260 (decreased)\n263 (increased)\n"));
+        assert_eq!(extract_title(page).as_deref(), Some("Sonar Sweep"));
+    }
+
+    #[test]
+    fn decodes_entities_in_examples() {
+        let page = "<pre><code>[&lt;&gt;({}){}]\n<em>&amp;&quot;</em>\n</code></pre>";
+
+        assert_eq!(extract_code_blocks(page), ["[<>({}){}]\n&\"\n"]);
+    }
+
+    #[test]
+    fn rejects_error_pages() {
+        let login = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+        let locked = "Please don't repeatedly request this endpoint before it unlocks! \
+                      The calendar countdown is synchronized with the server time; \
+                      the link will be enabled on the calendar the instant this puzzle becomes available.\n";
+
+        assert_eq!(check_response(400, login), Err(FetchError::ExpiredSession));
+        assert_eq!(check_response(404, locked), Err(FetchError::NotUnlocked));
+        assert_eq!(
+            check_response(500, "Internal Server Error"),
+            Err(FetchError::Status(500))
+        );
+        assert_eq!(check_response(200, ""), Err(FetchError::EmptyResponse));
+        assert_eq!(check_response(200, "1,2,3\n"), Ok(()));
+    }
 }
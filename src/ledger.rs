@@ -0,0 +1,307 @@
+use std::fs::{create_dir_all, read_to_string, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Context;
+
+use crate::answer::Answer;
+use crate::aoc::Part;
+use crate::input;
+use crate::submit::Verdict;
+
+/// Every answer submitted for a year so far, along with how it was judged.
+///
+/// Entries are stored one per line as `day`, `part`, `verdict` and `answer`
+/// separated by tabs, with any tabs, newlines or backslashes in the answer
+/// escaped.
+pub struct Ledger {
+    path: String,
+    entries: Vec<Entry>,
+}
+
+pub struct Entry {
+    pub day: usize,
+    pub part: Part,
+    pub verdict: Verdict,
+    pub answer: Answer,
+}
+
+/// What the ledger knows about an answer before it's submitted.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    Unknown,
+    Correct,
+    Wrong(String),
+}
+
+impl std::fmt::Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unknown => write!(f, "not submitted yet"),
+            Self::Correct => write!(f, "known correct"),
+            Self::Wrong(reason) => write!(f, "known wrong: {}", reason),
+        }
+    }
+}
+
+impl Ledger {
+    pub fn load(year: i32) -> anyhow::Result<Self> {
+        let path = format!("{}/submissions.tsv", input::cache_dir(year));
+        if !Path::new(&path).exists() {
+            return Ok(Self {
+                path,
+                entries: vec![],
+            });
+        }
+
+        let entries = read_to_string(&path)
+            .context("Failed to read submission ledger")?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(Entry::parse)
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self { path, entries })
+    }
+
+    /// Saves a judged submission. Verdicts that don't say anything about
+    /// the answer, like being rate limited, aren't recorded.
+    pub fn record(
+        &mut self,
+        day: usize,
+        part: Part,
+        answer: &Answer,
+        verdict: &Verdict,
+    ) -> anyhow::Result<()> {
+        if verdict_name(verdict).is_none() {
+            return Ok(());
+        }
+
+        let entry = Entry {
+            day,
+            part,
+            verdict: verdict.clone(),
+            answer: answer.clone(),
+        };
+
+        if let Some(folder) = Path::new(&self.path).parent() {
+            create_dir_all(folder).context("Failed to create input folder")?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .context("Failed to open submission ledger")?;
+        writeln!(file, "{}", entry).context("Failed to write to submission ledger")?;
+
+        self.entries.push(entry);
+
+        Ok(())
+    }
+
+    pub fn check(&self, day: usize, part: Part, answer: &Answer) -> Check {
+        let entries: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.day == day && entry.part == part)
+            .collect();
+
+        if let Some(correct) = entries
+            .iter()
+            .find(|entry| entry.verdict == Verdict::Correct)
+        {
+            return if &correct.answer == answer {
+                Check::Correct
+            } else {
+                Check::Wrong(format!("the correct answer is {}", correct.answer))
+            };
+        }
+
+        if let Some(repeat) = entries.iter().find(|entry| &entry.answer == answer) {
+            return Check::Wrong(format!("already submitted and was {}", repeat.verdict));
+        }
+
+        if let Some(value) = answer.as_integer() {
+            let bound = |verdict: Verdict| {
+                entries
+                    .iter()
+                    .filter(move |entry| entry.verdict == verdict)
+                    .filter_map(|entry| entry.answer.as_integer())
+            };
+
+            if let Some(too_high) = bound(Verdict::TooHigh).min().filter(|high| value >= *high) {
+                return Check::Wrong(format!("{} was already too high", too_high));
+            }
+            if let Some(too_low) = bound(Verdict::TooLow).max().filter(|low| value <= *low) {
+                return Check::Wrong(format!("{} was already too low", too_low));
+            }
+        }
+
+        Check::Unknown
+    }
+}
+
+impl Entry {
+    fn parse(line: &str) -> anyhow::Result<Self> {
+        let mut fields = line.splitn(4, '\t');
+        let mut next_field = |name: &str| {
+            fields
+                .next()
+                .with_context(|| format!("Ledger entry missing {}: {}", name, line))
+        };
+
+        let day = next_field("day")?.parse().context("Invalid ledger day")?;
+        let part = next_field("part")?.parse()?;
+        let verdict = match next_field("verdict")? {
+            "correct" => Verdict::Correct,
+            "too-high" => Verdict::TooHigh,
+            "too-low" => Verdict::TooLow,
+            "wrong" => Verdict::Wrong,
+            other => anyhow::bail!("Invalid ledger verdict: {}", other),
+        };
+        let answer = unescape(next_field("answer")?).parse()?;
+
+        Ok(Self {
+            day,
+            part,
+            verdict,
+            answer,
+        })
+    }
+}
+
+impl std::fmt::Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}",
+            self.day,
+            self.part,
+            verdict_name(&self.verdict).unwrap_or("wrong"),
+            escape(&self.answer.to_string())
+        )
+    }
+}
+
+/// Keeps an answer on one line and out of the other fields.
+fn escape(answer: &str) -> String {
+    answer
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape(field: &str) -> String {
+    let mut answer = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            answer.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('t') => answer.push('\t'),
+            Some('n') => answer.push('\n'),
+            Some('r') => answer.push('\r'),
+            Some(other) => answer.push(other),
+            None => answer.push('\\'),
+        }
+    }
+
+    answer
+}
+
+fn verdict_name(verdict: &Verdict) -> Option<&'static str> {
+    match verdict {
+        Verdict::Correct => Some("correct"),
+        Verdict::TooHigh => Some("too-high"),
+        Verdict::TooLow => Some("too-low"),
+        Verdict::Wrong => Some("wrong"),
+        Verdict::RateLimited(_) | Verdict::AlreadySolved => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Check, Entry, Ledger};
+    use crate::answer::Answer;
+    use crate::aoc::Part;
+    use crate::submit::Verdict;
+
+    fn ledger(entries: &[(Verdict, Answer)]) -> Ledger {
+        Ledger {
+            path: String::new(),
+            entries: entries
+                .iter()
+                .map(|(verdict, answer)| Entry {
+                    day: 1,
+                    part: Part::One,
+                    verdict: verdict.clone(),
+                    answer: answer.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn refuses_repeated_answers() {
+        let ledger = ledger(&[(Verdict::Wrong, "ABC".into())]);
+
+        assert_eq!(
+            ledger.check(1, Part::One, &"ABC".into()),
+            Check::Wrong("already submitted and was wrong".to_owned())
+        );
+        assert_eq!(ledger.check(1, Part::One, &"ABD".into()), Check::Unknown);
+        assert_eq!(ledger.check(1, Part::Two, &"ABC".into()), Check::Unknown);
+    }
+
+    #[test]
+    fn uses_too_high_and_too_low_as_bounds() {
+        let ledger = ledger(&[
+            (Verdict::TooHigh, 100u32.into()),
+            (Verdict::TooHigh, 80u32.into()),
+            (Verdict::TooLow, 20u32.into()),
+        ]);
+
+        assert_eq!(
+            ledger.check(1, Part::One, &90u32.into()),
+            Check::Wrong("80 was already too high".to_owned())
+        );
+        assert_eq!(
+            ledger.check(1, Part::One, &10u32.into()),
+            Check::Wrong("20 was already too low".to_owned())
+        );
+        assert_eq!(ledger.check(1, Part::One, &50u32.into()), Check::Unknown);
+    }
+
+    #[test]
+    fn knows_the_correct_answer() {
+        let ledger = ledger(&[
+            (Verdict::TooLow, 20u32.into()),
+            (Verdict::Correct, 42u32.into()),
+        ]);
+
+        assert_eq!(ledger.check(1, Part::One, &42u32.into()), Check::Correct);
+        assert_eq!(
+            ledger.check(1, Part::One, &43u32.into()),
+            Check::Wrong("the correct answer is 42".to_owned())
+        );
+    }
+
+    #[test]
+    fn escapes_text_answers() {
+        let entry = Entry {
+            day: 1,
+            part: Part::Two,
+            verdict: Verdict::Wrong,
+            answer: "a\tb\\c".into(),
+        };
+
+        let line = entry.to_string();
+        assert_eq!(line.lines().count(), 1);
+        assert_eq!(line.split('\t').count(), 4);
+        assert_eq!(Entry::parse(&line).unwrap().answer, entry.answer);
+    }
+}
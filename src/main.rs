@@ -1,79 +1,445 @@
 #![feature(
     array_windows,
-    path_try_exists,
     iter_intersperse,
     linked_list_remove,
     drain_filter,
     hash_drain_filter
 )]
 
-use chrono::{Datelike, Local};
-use clap::Parser;
+use anyhow::Context;
+use clap::{Parser, Subcommand};
 
+use crate::aoc::Part;
+use crate::ledger::{Check, Ledger};
+
+mod answer;
 mod aoc;
+mod batch;
+mod bench;
+mod cache;
+mod config;
+mod history;
 mod input;
+mod ledger;
+mod memory;
+mod ocr;
+mod output;
+mod runner;
+mod sandbox;
+mod scaffold;
+mod submit;
+mod unlock;
+mod verify;
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
 
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
+fn main() -> anyhow::Result<()> {
     dotenv::dotenv().ok();
 
     let args = Args::parse();
-    let day = args.day.unwrap_or_else(|| Local::now().day() as usize);
-
-    let day_impl = aoc::get_day(day)?;
-    let input_for_day = input::get_input(day).await?;
-
-    println!("Running part 1...");
-    let before_part1 = Local::now();
-    let part1 = day_impl.part1(&input_for_day)?;
-    let after_part1 = Local::now();
-    println!(
-        "Part 1: {} (in {} seconds)",
-        part1,
-        (after_part1 - before_part1).num_milliseconds() as f64 / 1000.0,
-    );
-
-    println!("Running part 2...");
-    let before_part2 = Local::now();
-    let part2 = day_impl.part2(&input_for_day)?;
-    let after_part2 = Local::now();
-    println!(
-        "Part 2: {} (in {} seconds)",
-        part2,
-        (after_part2 - before_part2).num_milliseconds() as f64 / 1000.0,
-    );
+    memory::set_profiling(args.profile_alloc);
+    runner::install_panic_hook();
+    let config = config::Config::load()?.merge(args.config());
+    config.validate()?;
+    config::init(config.clone());
+
+    let year = config.year.unwrap_or_else(aoc::latest_year);
+
+    // The memory limit applies to the whole process, so a sandboxed part
+    // runs before any runtime threads exist to share it
+    if let Some(Command::RunPart {
+        day,
+        part,
+        max_memory,
+    }) = args.command
+    {
+        return sandbox::serve_part(year, day, part, max_memory);
+    }
+
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .context("Failed to start the async runtime")?
+        .block_on(run(args, &config, year))
+}
+
+async fn run(args: Args, config: &config::Config, year: i32) -> anyhow::Result<()> {
+    let limits = config.limits();
+
+    match args.command {
+        Some(Command::Submit { day, part }) => {
+            let verdict = submit::solve_and_submit(year, day, part).await?;
+            println!("Verdict: {}", verdict);
+
+            return Ok(());
+        }
+        Some(Command::Verify { days }) => {
+            let selection = days.unwrap_or_else(runner::DaySelection::all);
+            return verify::verify(year, &selection, &limits).await;
+        }
+        Some(Command::Bench {
+            day,
+            days,
+            warmup,
+            runs,
+            compare,
+            threshold,
+        }) => {
+            let selection = match day {
+                Some(day) => runner::DaySelection(vec![day]),
+                None => days.unwrap_or_else(runner::DaySelection::all),
+            };
+            let options = bench::BenchOptions { warmup, runs };
+
+            let benches = bench::bench(year, &selection, &options).await;
+            bench::print_benches(&benches);
+
+            let mut history = history::BenchHistory::load(year)?;
+            let commit = history::current_commit();
+            let comparisons = history.compare(&commit, &benches, threshold);
+            for bench in &benches {
+                history.record(&commit, bench)?;
+            }
+
+            if compare {
+                println!();
+                if comparisons.is_empty() {
+                    println!("No baseline from another commit to compare against yet");
+                    return Ok(());
+                }
+
+                history::print_comparisons(&comparisons);
+                let regressions = comparisons.iter().filter(|c| c.regressed).count();
+                if regressions > 0 {
+                    anyhow::bail!(
+                        "{} step(s) got more than {}% slower since the baseline",
+                        regressions,
+                        threshold
+                    );
+                }
+            }
+
+            return Ok(());
+        }
+        Some(Command::RunPart { .. }) => unreachable!("handled before starting the runtime"),
+        Some(Command::NewDay { day }) => return scaffold::new_day(year, day).await,
+        Some(Command::Cache { command }) => {
+            return match command {
+                CacheCommand::List => cache::list(year),
+                CacheCommand::Clear { day } => cache::clear(year, day),
+                CacheCommand::Refetch { day } => cache::refetch(year, day).await,
+                CacheCommand::Verify => cache::verify(year),
+            };
+        }
+        Some(Command::Config {
+            command: ConfigCommand::Show,
+        }) => {
+            config::print_config(config);
+
+            return Ok(());
+        }
+        Some(Command::Batch { day, folder }) => {
+            let inputs = batch::load_inputs(&folder)?;
+            eprintln!("Running day {} on {} inputs...", day, inputs.len());
+
+            let outcomes = batch::run_batch(year, day, &inputs, &limits)?;
+            batch::print_batch(&outcomes);
+
+            let failures = outcomes
+                .iter()
+                .filter(|outcome| !outcome.problems().is_empty())
+                .count();
+            if failures > 0 {
+                anyhow::bail!("{} input(s) didn't give the expected answers", failures);
+            }
+
+            return Ok(());
+        }
+        None => {}
+    }
+
+    if args.list {
+        return runner::print_days(year);
+    }
+
+    if args.all || args.days.is_some() {
+        let selection = args.days.unwrap_or_else(runner::DaySelection::all);
+        let outcomes = runner::run_days(year, &selection, &limits).await;
+        match config.format {
+            Some(format) => output::print_records(format, &outcomes)?,
+            None => runner::print_summary(&outcomes, &Ledger::load(year)?),
+        }
+
+        return Ok(());
+    }
+
+    let day = if args.wait {
+        unlock::wait_for_unlock(year, args.day).await?
+    } else {
+        match args.day {
+            Some(day) => day,
+            None => unlock::current_day(year)?,
+        }
+    };
+
+    let day_impl = aoc::get_day(year, day)?;
+    let parts = match &args.part {
+        Some(part) => std::slice::from_ref(part),
+        None => &Part::BOTH[..],
+    };
+
+    let (input_for_day, ledger) = match (&args.input, args.example) {
+        // The submissions are for our own input, not whatever file was given
+        (Some(path), _) => (input::read_input(path)?, None),
+        (None, Some(None)) => {
+            return runner::run_examples(day, day_impl.as_ref(), parts, config.format)
+        }
+        // Submissions say nothing about an example's answers
+        (None, Some(Some(index))) => (input::get_example(year, day, index).await?, None),
+        (None, None) if args.wait => (
+            input::get_input_with_retries(year, day).await?,
+            Some(Ledger::load(year)?),
+        ),
+        (None, None) => (
+            input::get_input(year, day).await?,
+            Some(Ledger::load(year)?),
+        ),
+    };
+
+    let mut solver = runner::Solver::new(year, day, day_impl.as_ref(), &input_for_day, limits)?;
+
+    if let Some(format) = config.format {
+        let records: Vec<_> = parts
+            .iter()
+            .map(|&part| output::PartRecord::from_part(day, part, &solver.solve(part)))
+            .collect();
+
+        return output::write_records(format, &records);
+    }
+
+    // Limited parts parse in their own process, so the time is only known
+    // once one of them has run
+    let parsed_up_front = solver.parse.is_some();
+    if let Some(parse) = solver.parse {
+        println!("Parsed input (in {})", runner::format_duration(parse));
+    }
+
+    for &part in parts {
+        eprintln!("Running part {}...", part);
+        let outcome = solver.solve(part);
+        println!("Part {}: {}", part, outcome);
+
+        if let (Some(ledger), Some(answer)) = (&ledger, outcome.result.answer()) {
+            match ledger.check(day, part, answer) {
+                Check::Unknown => {}
+                check => println!("  ({})", check),
+            }
+        }
+    }
+
+    if let (false, Some(parse)) = (parsed_up_front, solver.parse) {
+        println!("Parsed input (in {})", runner::format_duration(parse));
+    }
 
     Ok(())
 }
 
 #[derive(Debug, Parser)]
 pub struct Args {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     #[clap(short, long)]
     day: Option<usize>,
+
+    /// Which year's puzzles to run, defaults to the latest one with solutions
+    #[clap(short, long)]
+    year: Option<i32>,
+
+    /// List the days with solutions for the year
+    #[clap(short, long)]
+    list: bool,
+
+    /// Run every day and print a summary table
+    #[clap(short, long, conflicts_with_all = &["day", "days"])]
+    all: bool,
+
+    /// Run a list of days and print a summary table, e.g. `3,5-9`
+    #[clap(long, conflicts_with = "day")]
+    days: Option<runner::DaySelection>,
+
+    /// Run the day against its declared examples, or against the Kth
+    /// example scraped from the puzzle page
+    #[clap(short, long, value_name = "K")]
+    example: Option<Option<usize>>,
+
+    /// Use the session and cache of this profile from the config
+    #[clap(long)]
+    profile: Option<String>,
+
+    /// Print results as `json` or `csv` instead of text
+    #[clap(short, long)]
+    format: Option<output::OutputFormat>,
+
+    /// Run the day on this file instead of the cached input, or on stdin
+    /// if it's `-`
+    #[clap(short, long, value_name = "PATH", conflicts_with_all = &["example", "all", "days", "wait"])]
+    input: Option<String>,
+
+    /// Only run this part of the day
+    #[clap(short, long, conflicts_with_all = &["all", "days"])]
+    part: Option<Part>,
+
+    /// Stop each part after this many seconds, running it in a child
+    /// process
+    #[clap(long, value_name = "SECONDS")]
+    timeout: Option<f64>,
+
+    /// Stop each part once it uses this many megabytes, running it in a
+    /// child process
+    #[clap(long, value_name = "MB")]
+    max_memory: Option<u64>,
+
+    /// Count the allocations each part makes and the most memory it has
+    /// live at once. Only the part's own thread is counted, not any threads
+    /// it spawns
+    #[clap(long)]
+    profile_alloc: bool,
+
+    /// Wait for the day (or the next day) to unlock, then fetch and run it
+    #[clap(short, long)]
+    wait: bool,
+}
+
+impl Args {
+    /// The settings given on the command line, which override the config.
+    fn config(&self) -> config::Config {
+        config::Config {
+            year: self.year,
+            profile: self.profile.clone(),
+            format: self.format,
+            timeout: self.timeout,
+            max_memory: self.max_memory,
+            ..config::Config::default()
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Solve a part and submit its answer
+    Submit {
+        #[clap(short, long)]
+        day: usize,
+
+        #[clap(short, long)]
+        part: Part,
+    },
+
+    /// Check answers against the expected ones in `answers.toml`, or
+    /// `answers.<profile>.toml` for a profile
+    Verify {
+        /// Only verify these days, e.g. `3,5-9`
+        #[clap(long)]
+        days: Option<runner::DaySelection>,
+    },
+
+    /// Time each part over many runs, along with parsing for days that
+    /// parse their input once for both parts
+    Bench {
+        #[clap(short, long, conflicts_with = "days")]
+        day: Option<usize>,
+
+        /// Only benchmark these days, e.g. `3,5-9`
+        #[clap(long)]
+        days: Option<runner::DaySelection>,
+
+        /// Untimed runs before measuring
+        #[clap(long, default_value = "3")]
+        warmup: usize,
+
+        /// Timed runs of each step
+        #[clap(long, default_value = "20")]
+        runs: usize,
+
+        /// Compare against the latest run from another commit, failing if
+        /// any step got slower
+        #[clap(long)]
+        compare: bool,
+
+        /// How many percent slower a step can get before it's flagged
+        #[clap(long, default_value = "10", value_name = "PERCENT")]
+        threshold: f64,
+    },
+
+    /// Run a single part with its input on stdin, for `--timeout` and
+    /// `--max-memory` to run in a child process
+    #[clap(setting = clap::AppSettings::Hidden)]
+    RunPart {
+        #[clap(short, long)]
+        day: usize,
+
+        #[clap(short, long)]
+        part: Part,
+
+        /// In bytes
+        #[clap(long)]
+        max_memory: Option<u64>,
+    },
+
+    /// Start a new day from the template, fetching its input and examples
+    NewDay { day: usize },
+
+    /// Manage the cached inputs for the year
+    Cache {
+        #[clap(subcommand)]
+        command: CacheCommand,
+    },
+
+    /// Inspect the settings from `aoc.toml`, the XDG config, the
+    /// environment and the command line
+    Config {
+        #[clap(subcommand)]
+        command: ConfigCommand,
+    },
+
+    /// Run a day on every `*.txt` input in a folder, checking each against
+    /// the answers in the `.toml` file with the same name
+    Batch {
+        #[clap(short, long)]
+        day: usize,
+
+        folder: std::path::PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CacheCommand {
+    /// Show each cached input with its size, when it was fetched and
+    /// whether it still matches its checksum
+    List,
+
+    /// Remove the cached inputs and examples
+    Clear {
+        /// Only remove this day's
+        #[clap(short, long)]
+        day: Option<usize>,
+    },
+
+    /// Fetch the cached inputs again, replacing them
+    Refetch {
+        /// Only fetch this day's, whether or not it's cached
+        #[clap(short, long)]
+        day: Option<usize>,
+    },
+
+    /// Check each cached input against the checksum from when it was
+    /// fetched
+    Verify,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Print the resolved settings, with the session token masked
+    Show,
 }
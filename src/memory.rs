@@ -0,0 +1,166 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// Passes every allocation through to the system allocator, counting them
+/// on the current thread while profiling is turned on.
+pub struct CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+static PROFILING: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static COUNTERS: Counters = const {
+        Counters {
+            allocations: Cell::new(0),
+            allocated_bytes: Cell::new(0),
+            live_bytes: Cell::new(0),
+            peak_live_bytes: Cell::new(0),
+        }
+    };
+}
+
+struct Counters {
+    allocations: Cell<u64>,
+    allocated_bytes: Cell<u64>,
+    live_bytes: Cell<i64>,
+    peak_live_bytes: Cell<i64>,
+}
+
+/// How much a part allocated while it ran.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+    pub peak_live_bytes: u64,
+}
+
+impl std::fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.allocated_bytes),
+            format_bytes(self.peak_live_bytes)
+        )
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+pub fn set_profiling(enabled: bool) {
+    PROFILING.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_profiling() -> bool {
+    PROFILING.load(Ordering::Relaxed)
+}
+
+/// Runs `f`, counting what it allocates on this thread if profiling is on.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !is_profiling() {
+        return (f(), None);
+    }
+
+    let start = COUNTERS.with(|counters| {
+        counters.peak_live_bytes.set(counters.live_bytes.get());
+
+        (
+            counters.allocations.get(),
+            counters.allocated_bytes.get(),
+            counters.live_bytes.get(),
+        )
+    });
+
+    let value = f();
+
+    let stats = COUNTERS.with(|counters| {
+        let (allocations, allocated_bytes, live_bytes) = start;
+
+        AllocStats {
+            allocations: counters.allocations.get() - allocations,
+            allocated_bytes: counters.allocated_bytes.get() - allocated_bytes,
+            peak_live_bytes: (counters.peak_live_bytes.get() - live_bytes).max(0) as u64,
+        }
+    });
+
+    (value, Some(stats))
+}
+
+/// Counts an allocation of `allocated` new bytes that changes how many
+/// bytes are live by `live_change`.
+fn record(allocated: usize, live_change: i64) {
+    if !is_profiling() {
+        return;
+    }
+
+    // Counters may already be gone while a thread is shutting down
+    let _ = COUNTERS.try_with(|counters| {
+        if allocated > 0 {
+            counters.allocations.set(counters.allocations.get() + 1);
+            counters
+                .allocated_bytes
+                .set(counters.allocated_bytes.get() + allocated as u64);
+        }
+
+        let live_bytes = counters.live_bytes.get() + live_change;
+        counters.live_bytes.set(live_bytes);
+        if live_bytes > counters.peak_live_bytes.get() {
+            counters.peak_live_bytes.set(live_bytes);
+        }
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), layout.size() as i64);
+        }
+
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), layout.size() as i64);
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, -(layout.size() as i64));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(new_size, new_size as i64 - layout.size() as i64);
+        }
+
+        new_ptr
+    }
+}
@@ -0,0 +1,133 @@
+use anyhow::Context;
+
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+/// Each glyph is followed by a single blank column.
+const GLYPH_SPACING: usize = GLYPH_WIDTH + 1;
+
+const GLYPHS: [(char, [&str; GLYPH_HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Reads the 4x6 block letters that some puzzles draw as their answer.
+///
+/// Any `#` or `█` in a row counts as a lit pixel; everything else is dark.
+/// Blank rows above and below the letters are ignored.
+pub fn recognize_grid<S: AsRef<str>>(rows: &[S]) -> anyhow::Result<String> {
+    let pixels: Vec<Vec<bool>> = rows
+        .iter()
+        .map(|row| row.as_ref().chars().map(|c| c == '#' || c == '█').collect())
+        .collect();
+
+    let first_row = pixels
+        .iter()
+        .position(|row| row.contains(&true))
+        .context("No letters to recognize")?;
+    let last_row = pixels
+        .iter()
+        .rposition(|row| row.contains(&true))
+        .unwrap_or(first_row);
+    let pixels = &pixels[first_row..=last_row];
+    if pixels.len() != GLYPH_HEIGHT {
+        anyhow::bail!(
+            "Letters must be {} rows tall, found {}",
+            GLYPH_HEIGHT,
+            pixels.len()
+        );
+    }
+
+    let width = pixels.iter().map(Vec::len).max().unwrap_or(0);
+    let lit = |x: usize, y: usize| pixels[y].get(x).cloned().unwrap_or(false);
+
+    (0..width)
+        .step_by(GLYPH_SPACING)
+        .map(|left| {
+            GLYPHS
+                .iter()
+                .find(|(_letter, glyph)| {
+                    glyph.iter().enumerate().all(|(y, glyph_row)| {
+                        glyph_row
+                            .chars()
+                            .enumerate()
+                            .all(|(x, pixel)| (pixel == '#') == lit(left + x, y))
+                    })
+                })
+                .map(|(letter, _glyph)| *letter)
+                .with_context(|| format!("Unrecognized letter at column {}", left))
+        })
+        .collect()
+}
+
+/// Reads block letters drawn as a set of lit `(x, y)` points, with the
+/// first letter starting at `x = 0`.
+pub fn recognize_points(
+    points: impl IntoIterator<Item = (usize, usize)>,
+) -> anyhow::Result<String> {
+    let points: Vec<(usize, usize)> = points.into_iter().collect();
+    let max_x = points.iter().map(|(x, _y)| *x).max().context("No points")?;
+    let max_y = points.iter().map(|(_x, y)| *y).max().context("No points")?;
+
+    let mut rows = vec![vec!['.'; max_x + 1]; max_y + 1];
+    for (x, y) in points {
+        rows[y][x] = '#';
+    }
+
+    let rows: Vec<String> = rows
+        .into_iter()
+        .map(|row| row.into_iter().collect())
+        .collect();
+    recognize_grid(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{recognize_grid, recognize_points};
+
+    #[test]
+    fn reads_a_full_code() {
+        let rows = [
+            "###..####.####.#..#.###...##..#..#.#..#",
+            "#..#....#.#....#..#.#..#.#..#.#.#..#..#",
+            "#..#...#..###..####.#..#.#..#.##...#..#",
+            "###...#...#....#..#.###..####.#.#..#..#",
+            "#....#....#....#..#.#.#..#..#.#.#..#..#",
+            "#....####.####.#..#.#..#.#..#.#..#..##.",
+        ];
+
+        assert_eq!(recognize_grid(&rows).unwrap(), "PZEHRAKU");
+    }
+
+    #[test]
+    fn ignores_blank_rows_and_reads_block_characters() {
+        let rows = ["", ".██.", "█..█", "█..█", "█..█", "█..█", ".██.", "...."];
+
+        assert_eq!(recognize_grid(&rows).unwrap(), "O");
+    }
+
+    #[test]
+    fn rejects_the_square_from_the_day_13_example() {
+        // The example folds into a 5x5 square rather than a letter, which
+        // day 13 shows as a grid instead
+        let square = (0..5)
+            .flat_map(|y| (0..5).map(move |x| (x, y)))
+            .filter(|&(x, y)| x == 0 || x == 4 || y == 0 || y == 4);
+
+        assert!(recognize_points(square).is_err());
+    }
+}
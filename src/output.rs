@@ -0,0 +1,148 @@
+use std::str::FromStr;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+use crate::aoc::Part;
+use crate::runner::{DayOutcome, PartOutcome, PartResult};
+
+/// A machine-readable format for results, instead of the usual text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Json,
+    Csv,
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json => write!(f, "json"),
+            Self::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(format: &str) -> anyhow::Result<Self> {
+        match format.trim() {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => anyhow::bail!("Format must be json or csv, got {}", format),
+        }
+    }
+}
+
+/// One part's result, flattened for scripts to read.
+#[derive(Debug, Serialize)]
+pub struct PartRecord {
+    pub day: usize,
+    /// Which declared example was run, if any.
+    pub example: Option<usize>,
+    pub part: usize,
+    pub answer: Option<String>,
+    pub duration_seconds: Option<f64>,
+    pub allocations: Option<u64>,
+    pub allocated_bytes: Option<u64>,
+    pub peak_live_bytes: Option<u64>,
+    pub status: &'static str,
+    pub error: Option<String>,
+}
+
+impl PartRecord {
+    pub fn from_outcomes(outcomes: &[DayOutcome]) -> Vec<Self> {
+        outcomes
+            .iter()
+            .flat_map(|outcome| {
+                Part::BOTH.map(|part| match &outcome.parts {
+                    Err(error) => Self {
+                        day: outcome.day,
+                        example: None,
+                        part: part.number(),
+                        answer: None,
+                        duration_seconds: None,
+                        allocations: None,
+                        allocated_bytes: None,
+                        peak_live_bytes: None,
+                        status: "error",
+                        error: Some(format!("{:#}", error)),
+                    },
+                    Ok(parts) => Self::from_part(outcome.day, part, &parts[part.number() - 1]),
+                })
+            })
+            .collect()
+    }
+
+    pub fn from_part(day: usize, part: Part, outcome: &PartOutcome) -> Self {
+        let (status, error) = match &outcome.result {
+            PartResult::Solved(_) => ("solved", None),
+            PartResult::NotImplemented => ("not-implemented", None),
+            PartResult::Failed(error) => ("failed", Some(format!("{:#}", error))),
+            PartResult::Panicked(message) => ("panicked", Some(message.clone())),
+            PartResult::TimedOut(_) => ("timed-out", None),
+            PartResult::OutOfMemory => ("out-of-memory", None),
+        };
+
+        let stats = outcome.allocations;
+        Self {
+            day,
+            example: None,
+            part: part.number(),
+            answer: outcome.result.answer().map(ToString::to_string),
+            duration_seconds: Some(outcome.duration.as_secs_f64()),
+            allocations: stats.map(|stats| stats.allocations),
+            allocated_bytes: stats.map(|stats| stats.allocated_bytes),
+            peak_live_bytes: stats.map(|stats| stats.peak_live_bytes),
+            status,
+            error,
+        }
+    }
+
+    /// A part's result on one of the day's declared examples, which counts
+    /// as a wrong answer if it doesn't match the expected one.
+    pub fn from_example(
+        day: usize,
+        example: usize,
+        part: Part,
+        outcome: &PartOutcome,
+        expected: Option<&Answer>,
+    ) -> Self {
+        let mut record = Self::from_part(day, part, outcome);
+        record.example = Some(example);
+
+        if let Some(expected) = expected {
+            if outcome.result.answer() != Some(expected) {
+                record.status = "wrong-answer";
+                record.error = Some(format!("expected {}", expected));
+            }
+        }
+
+        record
+    }
+}
+
+/// Prints every part of the outcomes as a JSON array or as CSV rows.
+pub fn print_records(format: OutputFormat, outcomes: &[DayOutcome]) -> anyhow::Result<()> {
+    write_records(format, &PartRecord::from_outcomes(outcomes))
+}
+
+pub fn write_records(format: OutputFormat, records: &[PartRecord]) -> anyhow::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&records).context("Failed to write JSON")?;
+            println!("{}", json);
+        }
+        OutputFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(std::io::stdout());
+            for record in records {
+                writer.serialize(record).context("Failed to write CSV")?;
+            }
+            writer.flush().context("Failed to write CSV")?;
+        }
+    }
+
+    Ok(())
+}
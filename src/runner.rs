@@ -0,0 +1,579 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+
+use crate::answer::Answer;
+use crate::aoc::{self, Aoc, NotImplemented, Part, Prepared};
+use crate::input;
+use crate::ledger::{Check, Ledger};
+use crate::memory::{self, AllocStats};
+use crate::output::{self, OutputFormat, PartRecord};
+use crate::sandbox::{self, Limits};
+
+/// A set of days to run, parsed from a list like `3,5-9`.
+#[derive(Debug, Clone)]
+pub struct DaySelection(pub Vec<usize>);
+
+impl DaySelection {
+    pub fn all() -> Self {
+        Self((1..=25).collect())
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = anyhow::Error;
+
+    fn from_str(selection: &str) -> anyhow::Result<Self> {
+        let mut days = Vec::new();
+
+        for item in selection.split(',').map(str::trim) {
+            if let Some((start, end)) = item.split_once('-') {
+                let start: usize = start.trim().parse().context("Invalid start of day range")?;
+                let end: usize = end.trim().parse().context("Invalid end of day range")?;
+                if start > end {
+                    anyhow::bail!("Day range {} is backwards", item);
+                }
+
+                days.extend(start..=end);
+            } else {
+                days.push(item.parse().context("Invalid day")?);
+            }
+        }
+
+        if let Some(day) = days.iter().find(|day| !(1..=25).contains(*day)) {
+            anyhow::bail!("Day {} is outside of 1-25", day);
+        }
+
+        days.sort();
+        days.dedup();
+
+        Ok(Self(days))
+    }
+}
+
+thread_local! {
+    /// Whether this thread is running a step whose panics are caught and
+    /// reported as its result.
+    static CATCHING_PANICS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Keeps panics quiet while a step is running, since they're reported as
+/// its result, and prints any others as usual. This is installed once for
+/// the whole run, as swapping hooks around each step races between threads.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !CATCHING_PANICS.with(Cell::get) {
+            default_hook(info);
+        }
+    }));
+}
+
+pub enum PartResult {
+    Solved(Answer),
+    NotImplemented,
+    Failed(anyhow::Error),
+    Panicked(String),
+    TimedOut(Duration),
+    OutOfMemory,
+}
+
+impl PartResult {
+    fn from_panic(payload: Box<dyn Any + Send>) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_owned());
+
+        // `unimplemented!()` and `todo!()` panic with these messages
+        if message.starts_with("not implemented") || message.starts_with("not yet implemented") {
+            Self::NotImplemented
+        } else {
+            Self::Panicked(message)
+        }
+    }
+
+    /// Runs a step, catching any panic so that the rest of the run can
+    /// continue. Anything but a value comes back as the part's result.
+    pub fn catch<T>(step: impl FnOnce() -> anyhow::Result<T>) -> Result<T, Self> {
+        let was_catching = CATCHING_PANICS.with(|catching| catching.replace(true));
+        let result = panic::catch_unwind(AssertUnwindSafe(step));
+        CATCHING_PANICS.with(|catching| catching.set(was_catching));
+
+        match result {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(error)) if error.is::<NotImplemented>() => Err(Self::NotImplemented),
+            Ok(Err(error)) => Err(Self::Failed(error)),
+            Err(payload) => Err(Self::from_panic(payload)),
+        }
+    }
+
+    pub fn answer(&self) -> Option<&Answer> {
+        match self {
+            Self::Solved(answer) => Some(answer),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PartResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Solved(answer) => write!(f, "{}", answer),
+            Self::NotImplemented => write!(f, "not implemented"),
+            Self::Failed(error) => write!(f, "error: {:#}", error),
+            Self::Panicked(message) => write!(f, "panicked: {}", message),
+            Self::TimedOut(timeout) => write!(f, "timed out after {}", format_duration(*timeout)),
+            Self::OutOfMemory => write!(f, "ran out of memory"),
+        }
+    }
+}
+
+pub struct PartOutcome {
+    pub result: PartResult,
+    pub duration: Duration,
+    /// What the part allocated, when running with `--profile-alloc`.
+    pub allocations: Option<AllocStats>,
+}
+
+impl PartOutcome {
+    /// Runs a single part, catching any panic so that the rest of the
+    /// run can continue.
+    pub fn run(solve: impl FnOnce() -> anyhow::Result<Answer>) -> Self {
+        let mut duration = Duration::ZERO;
+        let mut allocations = None;
+        let result = PartResult::catch(|| {
+            let (answer, stats) = memory::measure(|| {
+                let start = Instant::now();
+                let answer = solve();
+                duration = start.elapsed();
+
+                answer
+            });
+            allocations = stats;
+
+            answer
+        });
+
+        Self {
+            result: result.map_or_else(|result| result, PartResult::Solved),
+            duration,
+            allocations,
+        }
+    }
+
+    /// How long the part took, along with what it allocated if profiled.
+    fn cost(&self) -> String {
+        match &self.allocations {
+            Some(allocations) => format!("{}, {}", format_duration(self.duration), allocations),
+            None => format_duration(self.duration),
+        }
+    }
+
+    /// What the part allocated, for a table column of its own.
+    pub fn allocation_summary(&self) -> String {
+        self.allocations
+            .map_or_else(|| "-".to_owned(), |allocations| allocations.to_string())
+    }
+}
+
+impl std::fmt::Display for PartOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.result {
+            PartResult::Solved(answer) => {
+                let duration = self.cost();
+                match answer {
+                    // Grids are printed below the label so their rows line up
+                    Answer::Grid(_) => write!(f, "(in {})\n{}", duration, answer),
+                    _ => write!(f, "{} (in {})", answer, duration),
+                }
+            }
+            other => write!(f, "{}", other),
+        }
+    }
+}
+
+pub struct DayOutcome {
+    pub day: usize,
+    /// How long parsing took, for days that parse before solving.
+    pub parse: Option<Duration>,
+    pub parts: anyhow::Result<[PartOutcome; 2]>,
+}
+
+/// Parses a day's input once for both parts, timing it on its own when
+/// the day parses up front.
+pub fn prepare<'a>(
+    day_impl: &'a dyn Aoc,
+    input: &'a str,
+) -> anyhow::Result<(Box<dyn Prepared + 'a>, Option<Duration>)> {
+    let mut duration = Duration::ZERO;
+    let prepared = PartResult::catch(|| {
+        let start = Instant::now();
+        let prepared = day_impl.prepare(input);
+        duration = start.elapsed();
+
+        prepared
+    })
+    .map_err(|result| match result {
+        PartResult::Failed(error) => error.context("Failed to parse input"),
+        other => anyhow::anyhow!("Failed to parse input: {}", other),
+    })?;
+
+    let parse = prepared.is_parsed().then_some(duration);
+
+    Ok((prepared, parse))
+}
+
+/// Solves a day's parts, either here from input parsed once up front, or
+/// each in a child process when there are limits to enforce.
+pub struct Solver<'a> {
+    year: i32,
+    day: usize,
+    input: &'a str,
+    limits: Limits,
+    prepared: Option<Box<dyn Prepared + 'a>>,
+    /// How long parsing took, once it's known.
+    pub parse: Option<Duration>,
+}
+
+impl<'a> Solver<'a> {
+    pub fn new(
+        year: i32,
+        day: usize,
+        day_impl: &'a dyn Aoc,
+        input: &'a str,
+        limits: Limits,
+    ) -> anyhow::Result<Self> {
+        // Limited parts parse in their own process, in case parsing is
+        // what runs away
+        let (prepared, parse) = if limits.is_set() {
+            (None, None)
+        } else {
+            let (prepared, parse) = prepare(day_impl, input)?;
+            (Some(prepared), parse)
+        };
+
+        Ok(Self {
+            year,
+            day,
+            input,
+            limits,
+            prepared,
+            parse,
+        })
+    }
+
+    pub fn solve(&mut self, part: Part) -> PartOutcome {
+        match &self.prepared {
+            Some(prepared) => PartOutcome::run(|| prepared.solve(part)),
+            None => {
+                let (parse, outcome) =
+                    sandbox::run_part(self.year, self.day, part, self.input, &self.limits);
+                self.parse = self.parse.or(parse);
+
+                outcome
+            }
+        }
+    }
+
+    pub fn solve_both(&mut self) -> [PartOutcome; 2] {
+        Part::BOTH.map(|part| self.solve(part))
+    }
+}
+
+pub async fn run_day(year: i32, day: usize, limits: &Limits) -> DayOutcome {
+    let mut parse = None;
+    let parts = async {
+        let day_impl = aoc::get_day(year, day)?;
+        let input_for_day = input::get_input(year, day).await?;
+
+        let mut solver = Solver::new(year, day, day_impl.as_ref(), &input_for_day, *limits)?;
+        let parts = solver.solve_both();
+        parse = solver.parse;
+
+        Ok(parts)
+    }
+    .await;
+
+    DayOutcome { day, parse, parts }
+}
+
+/// Runs the given parts of a day against the examples it declares, failing
+/// if any of them give the wrong answer.
+pub fn run_examples(
+    day: usize,
+    day_impl: &dyn Aoc,
+    parts: &[Part],
+    format: Option<OutputFormat>,
+) -> anyhow::Result<()> {
+    let examples = day_impl.examples();
+    if examples.is_empty() {
+        anyhow::bail!("No examples declared for this day");
+    }
+
+    let mut failures = 0;
+    let mut records = Vec::new();
+    for (index, example) in examples.iter().enumerate() {
+        if format.is_none() {
+            println!("Example {}:", index + 1);
+        }
+
+        for &part in parts {
+            let outcome = PartOutcome::run(|| day_impl.solve(part, example.input));
+            let expected = example.expected(part)?;
+            let passed = expected
+                .as_ref()
+                .map(|expected| outcome.result.answer() == Some(expected));
+            if passed == Some(false) {
+                failures += 1;
+            }
+
+            if format.is_some() {
+                records.push(PartRecord::from_example(
+                    day,
+                    index + 1,
+                    part,
+                    &outcome,
+                    expected.as_ref(),
+                ));
+                continue;
+            }
+
+            let check = match (passed, &expected) {
+                (Some(true), _) => " [pass]".to_owned(),
+                (Some(false), Some(expected)) => format!(" [FAIL, expected {}]", expected),
+                _ => String::new(),
+            };
+            println!("Part {}: {}{}", part, outcome, check);
+        }
+    }
+
+    if let Some(format) = format {
+        output::write_records(format, &records)?;
+    }
+
+    if failures > 0 {
+        anyhow::bail!("{} example answer(s) didn't match", failures);
+    }
+
+    Ok(())
+}
+
+pub async fn run_days(year: i32, selection: &DaySelection, limits: &Limits) -> Vec<DayOutcome> {
+    let mut outcomes = Vec::new();
+    for &day in &selection.0 {
+        eprintln!("Running day {}...", day);
+        outcomes.push(run_day(year, day, limits).await);
+    }
+
+    outcomes
+}
+
+/// Formats a duration in whichever unit keeps it readable, from
+/// nanoseconds up to seconds.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.3}s", duration.as_secs_f64())
+    }
+}
+
+pub fn print_summary(outcomes: &[DayOutcome], ledger: &Ledger) {
+    let rows: Vec<[String; 9]> = outcomes
+        .iter()
+        .map(|outcome| summary_row(outcome, ledger))
+        .collect();
+
+    print_part_table("Day", rows);
+}
+
+/// Prints a table with a row per day or input and a column for each
+/// part's answer and time, along with what they allocated when profiling.
+pub fn print_part_table(label: &str, rows: Vec<[String; 9]>) {
+    if memory::is_profiling() {
+        let header = [
+            label, "Parse", "Part 1", "Time", "Memory", "Part 2", "Time", "Memory", "Status",
+        ]
+        .map(String::from);
+
+        return print_table(header, &rows);
+    }
+
+    let header = [label, "Parse", "Part 1", "Time", "Part 2", "Time", "Status"].map(String::from);
+    let rows: Vec<[String; 7]> = rows
+        .into_iter()
+        .map(
+            |[name, parse, answer1, time1, _, answer2, time2, _, status]| {
+                [name, parse, answer1, time1, answer2, time2, status]
+            },
+        )
+        .collect();
+
+    print_table(header, &rows);
+}
+
+/// Lists the registered days of a year along with what they implement.
+pub fn print_days(year: i32) -> anyhow::Result<()> {
+    let days = aoc::days(year);
+    if days.is_empty() {
+        anyhow::bail!("No days registered for {}", year);
+    }
+
+    let header = ["Day", "Title", "Parts", "Examples"].map(String::from);
+    let rows: Vec<[String; 4]> = days
+        .iter()
+        .map(|entry| {
+            let parts: Vec<String> = entry.parts.iter().map(Part::to_string).collect();
+            let examples = (entry.new)().examples().len();
+
+            [
+                entry.day.to_string(),
+                entry.title.to_owned(),
+                if parts.is_empty() {
+                    "-".to_owned()
+                } else {
+                    parts.join(", ")
+                },
+                examples.to_string(),
+            ]
+        })
+        .collect();
+
+    print_table(header, &rows);
+
+    Ok(())
+}
+
+pub fn print_table<const N: usize>(header: [String; N], rows: &[[String; N]]) {
+    let mut widths = header.clone().map(|cell| cell.chars().count());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = std::cmp::max(*width, cell.chars().count());
+        }
+    }
+
+    let print_row = |row: &[String; N]| {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        println!("{}", cells.join(" | ").trim_end());
+    };
+
+    print_row(&header);
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-+-")
+    );
+    rows.iter().for_each(print_row);
+}
+
+fn summary_row(outcome: &DayOutcome, ledger: &Ledger) -> [String; 9] {
+    let day = outcome.day.to_string();
+    let parse = outcome
+        .parse
+        .map(format_duration)
+        .unwrap_or_else(|| "-".to_owned());
+
+    match &outcome.parts {
+        Err(error) => [
+            day,
+            parse,
+            "-".to_owned(),
+            "-".to_owned(),
+            "-".to_owned(),
+            "-".to_owned(),
+            "-".to_owned(),
+            "-".to_owned(),
+            format!("error: {}", error),
+        ],
+        Ok([part1, part2]) => {
+            let describe = |part: &PartOutcome| {
+                let answer = part
+                    .result
+                    .answer()
+                    .map_or_else(|| "-".to_owned(), Answer::summary);
+
+                (
+                    answer,
+                    format_duration(part.duration),
+                    part.allocation_summary(),
+                )
+            };
+            let (answer1, time1, memory1) = describe(part1);
+            let (answer2, time2, memory2) = describe(part2);
+
+            let problems: Vec<String> = Part::BOTH
+                .iter()
+                .zip([part1, part2])
+                .filter_map(|(&part, part_outcome)| {
+                    let problem = match part_outcome.result.answer() {
+                        None => part_outcome.result.to_string(),
+                        Some(answer) => match ledger.check(outcome.day, part, answer) {
+                            check @ Check::Wrong(_) => check.to_string(),
+                            _ => return None,
+                        },
+                    };
+
+                    Some(format!("part {}: {}", part, problem))
+                })
+                .collect();
+            let status = if problems.is_empty() {
+                "ok".to_owned()
+            } else {
+                problems.join("; ")
+            };
+
+            [
+                day, parse, answer1, time1, memory1, answer2, time2, memory2, status,
+            ]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DaySelection;
+
+    fn days(selection: &str) -> Vec<usize> {
+        selection.parse::<DaySelection>().unwrap().0
+    }
+
+    #[test]
+    fn parses_days_and_ranges() {
+        assert_eq!(days("3"), vec![3]);
+        assert_eq!(days("3,5-9"), vec![3, 5, 6, 7, 8, 9]);
+        assert_eq!(days(" 1 - 3 , 25 "), vec![1, 2, 3, 25]);
+        assert_eq!(days("7-7"), vec![7]);
+    }
+
+    #[test]
+    fn sorts_and_removes_duplicates() {
+        assert_eq!(days("9,2-4,3,9"), vec![2, 3, 4, 9]);
+    }
+
+    #[test]
+    fn rejects_bad_selections() {
+        assert!("9-5".parse::<DaySelection>().is_err());
+        assert!("0".parse::<DaySelection>().is_err());
+        assert!("20-26".parse::<DaySelection>().is_err());
+        assert!("3,x".parse::<DaySelection>().is_err());
+        assert!("".parse::<DaySelection>().is_err());
+    }
+}
@@ -0,0 +1,260 @@
+use std::io::{Read, Write};
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::aoc::{self, Part};
+use crate::memory::{self, AllocStats};
+use crate::runner::{self, PartOutcome, PartResult};
+
+/// Limits for running each part, which put it in a child process so a
+/// runaway part can be stopped without stopping the whole run.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Limits {
+    pub timeout: Option<Duration>,
+    /// The most memory a part can use, in bytes.
+    pub max_memory: Option<u64>,
+}
+
+impl Limits {
+    pub fn is_set(&self) -> bool {
+        self.timeout.is_some() || self.max_memory.is_some()
+    }
+}
+
+/// What a child process sends back after running a part, as the last
+/// line of its stdout.
+#[derive(Debug, Serialize, Deserialize)]
+struct Report {
+    parse_seconds: Option<f64>,
+    duration_seconds: f64,
+    allocations: Option<AllocStats>,
+    status: String,
+    answer: Option<String>,
+    error: Option<String>,
+}
+
+/// Runs a part in a child process under the given limits, returning how
+/// long parsing took along with the part's outcome.
+pub fn run_part(
+    year: i32,
+    day: usize,
+    part: Part,
+    input: &str,
+    limits: &Limits,
+) -> (Option<Duration>, PartOutcome) {
+    match run_child(year, day, part, input, limits) {
+        Ok(result) => result,
+        Err(error) => (
+            None,
+            PartOutcome {
+                result: PartResult::Failed(error),
+                duration: Duration::ZERO,
+                allocations: None,
+            },
+        ),
+    }
+}
+
+fn run_child(
+    year: i32,
+    day: usize,
+    part: Part,
+    input: &str,
+    limits: &Limits,
+) -> anyhow::Result<(Option<Duration>, PartOutcome)> {
+    let exe = std::env::current_exe().context("Couldn't find the runner executable")?;
+    let mut command = Command::new(exe);
+    if memory::is_profiling() {
+        command.arg("--profile-alloc");
+    }
+    command
+        .args(["--year", &year.to_string(), "run-part"])
+        .args(["--day", &day.to_string(), "--part", &part.to_string()]);
+    if let Some(max_memory) = limits.max_memory {
+        command.args(["--max-memory", &max_memory.to_string()]);
+    }
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to start a child process for the part")?;
+
+    // The pipes are drained on their own threads so a chatty part can't
+    // fill one up and stall while we wait on it
+    let mut stdin = child.stdin.take().context("Missing child stdin")?;
+    let input = input.to_owned();
+    let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let stdout = read_in_background(child.stdout.take().context("Missing child stdout")?);
+    let stderr = read_in_background(child.stderr.take().context("Missing child stderr")?);
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child
+            .try_wait()
+            .context("Failed to wait on child process")?
+        {
+            break status;
+        }
+
+        if let Some(timeout) = limits.timeout {
+            if start.elapsed() >= timeout {
+                child.kill().ok();
+                child.wait().ok();
+
+                return Ok((
+                    None,
+                    PartOutcome {
+                        result: PartResult::TimedOut(timeout),
+                        duration: start.elapsed(),
+                        allocations: None,
+                    },
+                ));
+            }
+        }
+
+        thread::sleep(Duration::from_millis(5));
+    };
+    let elapsed = start.elapsed();
+
+    // The child may exit before reading all of its input, which is fine
+    writer.join().ok();
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    // Going over the limit aborts with a message, while the OOM killer
+    // sends a SIGKILL without one
+    if stderr.contains("memory allocation of") || status.signal() == Some(libc::SIGKILL) {
+        return Ok((
+            None,
+            PartOutcome {
+                result: PartResult::OutOfMemory,
+                duration: elapsed,
+                allocations: None,
+            },
+        ));
+    }
+
+    let report = stdout
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .and_then(|line| serde_json::from_str::<Report>(line).ok());
+    let report = match report {
+        Some(report) => report,
+        None => anyhow::bail!(
+            "The child process exited with {} without a result: {}",
+            status,
+            stderr.trim()
+        ),
+    };
+
+    Ok((
+        report.parse_seconds.map(Duration::from_secs_f64),
+        PartOutcome {
+            duration: Duration::from_secs_f64(report.duration_seconds),
+            allocations: report.allocations,
+            result: report.into_result()?,
+        },
+    ))
+}
+
+fn read_in_background(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut output = String::new();
+        pipe.read_to_string(&mut output).ok();
+
+        output
+    })
+}
+
+impl Report {
+    fn into_result(self) -> anyhow::Result<PartResult> {
+        let error = self.error.unwrap_or_default();
+
+        Ok(match self.status.as_str() {
+            "solved" => PartResult::Solved(
+                self.answer
+                    .context("The child process solved the part without an answer")?
+                    .parse()?,
+            ),
+            "not-implemented" => PartResult::NotImplemented,
+            "panicked" => PartResult::Panicked(error),
+            _ => PartResult::Failed(anyhow::anyhow!(error)),
+        })
+    }
+}
+
+/// The child's side of [`run_part`]: reads the input from stdin, limits
+/// its own memory, and prints a report of the part's outcome.
+pub fn serve_part(
+    year: i32,
+    day: usize,
+    part: Part,
+    max_memory: Option<u64>,
+) -> anyhow::Result<()> {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .context("Failed to read input from stdin")?;
+
+    if let Some(max_memory) = max_memory {
+        limit_memory(max_memory)?;
+    }
+
+    let day_impl = aoc::get_day(year, day)?;
+    let (parse, outcome) = match runner::prepare(day_impl.as_ref(), &input) {
+        Ok((prepared, parse)) => (parse, PartOutcome::run(|| prepared.solve(part))),
+        Err(error) => (
+            None,
+            PartOutcome {
+                result: PartResult::Failed(error),
+                duration: Duration::ZERO,
+                allocations: None,
+            },
+        ),
+    };
+
+    let (status, answer, error) = match outcome.result {
+        PartResult::Solved(answer) => ("solved", Some(answer.to_string()), None),
+        PartResult::NotImplemented => ("not-implemented", None, None),
+        PartResult::Panicked(message) => ("panicked", None, Some(message)),
+        PartResult::Failed(error) => ("failed", None, Some(format!("{:#}", error))),
+        other => ("failed", None, Some(other.to_string())),
+    };
+    let report = Report {
+        parse_seconds: parse.map(|parse| parse.as_secs_f64()),
+        duration_seconds: outcome.duration.as_secs_f64(),
+        allocations: outcome.allocations,
+        status: status.to_owned(),
+        answer,
+        error,
+    };
+
+    println!();
+    println!("{}", serde_json::to_string(&report)?);
+
+    Ok(())
+}
+
+/// Caps the data this process can allocate, so going over makes the
+/// allocation fail instead of taking down the machine.
+fn limit_memory(bytes: u64) -> anyhow::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: bytes as libc::rlim_t,
+        rlim_max: bytes as libc::rlim_t,
+    };
+
+    // SAFETY: `setrlimit` only reads the struct we pass it
+    if unsafe { libc::setrlimit(libc::RLIMIT_DATA, &limit) } != 0 {
+        return Err(std::io::Error::last_os_error()).context("Failed to set the memory limit");
+    }
+
+    Ok(())
+}
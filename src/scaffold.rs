@@ -0,0 +1,132 @@
+use std::fs::{create_dir_all, write};
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use crate::{aoc, input, unlock};
+
+/// The starting point for a new day. The parts report themselves as not
+/// implemented until they're written and added to `parts`, and the example
+/// is left to paste in from the puzzle page along with its answers.
+const TEMPLATE: &str = r#"use crate::answer::Answer;
+use crate::aoc::{Example, NotImplemented, Solution};
+
+pub struct Day__DAY__;
+
+crate::aoc::register_day! {
+    year: __YEAR__,
+    day: __DAY__,
+    title: __TITLE__,
+    parts: [],
+    solution: Day__DAY__,
+}
+
+const EXAMPLE: &str = "\
+";
+
+impl Solution for Day__DAY__ {
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part1: None,
+            part2: None,
+        }]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        Ok(input.lines().collect())
+    }
+
+    fn part1(&self, _lines: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        Err(NotImplemented.into())
+    }
+
+    fn part2(&self, _lines: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        Err(NotImplemented.into())
+    }
+}
+
+crate::aoc::example_tests!(Day__DAY__);
+"#;
+
+/// What we could learn about the day from its puzzle page.
+#[derive(Default)]
+struct PuzzleInfo {
+    title: Option<String>,
+}
+
+/// Creates `src/yearYYYY/dayN.rs` from the template, which registers
+/// itself on the next build. The input and examples are fetched too
+/// when a session is available.
+pub async fn new_day(year: i32, day: usize) -> anyhow::Result<()> {
+    unlock::validate_day(day)?;
+
+    let folder = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join(format!("year{}", year));
+    let path = folder.join(format!("day{}.rs", day));
+    // A registered day without any parts is only a placeholder, so it's
+    // fine to start it over
+    let placeholder = aoc::days(year)
+        .into_iter()
+        .find(|entry| entry.day == day)
+        .map(|entry| entry.parts.is_empty());
+    if placeholder != Some(true) && (placeholder.is_some() || path.exists()) {
+        anyhow::bail!(
+            "Day {} of {} already exists at {}, refusing to overwrite it",
+            day,
+            year,
+            path.display()
+        );
+    }
+
+    let info = if input::session_cookie().is_ok() {
+        fetch_puzzle_info(year, day).await
+    } else {
+        eprintln!("No SESSION set, skipping the input and examples");
+        PuzzleInfo::default()
+    };
+
+    let title = info.title.unwrap_or_else(|| format!("Day {}", day));
+    let source = TEMPLATE
+        .replace("__YEAR__", &year.to_string())
+        .replace("__DAY__", &day.to_string())
+        .replace("__TITLE__", &format!("{:?}", title));
+
+    create_dir_all(&folder).context("Failed to create source folder")?;
+    write(&path, source).context("Failed to write the new day")?;
+    println!(
+        "Created {}, rebuild to run it with `--day {}`",
+        path.display(),
+        day
+    );
+
+    Ok(())
+}
+
+/// Fetches and caches the day's input and examples, only warning on
+/// failure so the day still gets created.
+async fn fetch_puzzle_info(year: i32, day: usize) -> PuzzleInfo {
+    if let Err(error) = input::get_input(year, day).await {
+        eprintln!("Couldn't fetch the input: {:#}", error);
+    }
+
+    let page = match input::retrieve_puzzle_page(year, day).await {
+        Ok(page) => page,
+        Err(error) => {
+            eprintln!("Couldn't fetch the puzzle page: {:#}", error);
+            return PuzzleInfo::default();
+        }
+    };
+
+    let examples = input::extract_code_blocks(&page);
+    if let Err(error) = input::save_examples(year, day, &examples) {
+        eprintln!("Couldn't save the examples: {:#}", error);
+    }
+
+    PuzzleInfo {
+        title: input::extract_title(&page),
+    }
+}
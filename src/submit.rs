@@ -0,0 +1,188 @@
+use std::time::Duration;
+
+use anyhow::Context;
+
+use crate::answer::Answer;
+use crate::aoc::{self, Part};
+use crate::input;
+use crate::ledger::{Check, Ledger};
+use crate::runner::{PartOutcome, PartResult};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited(Duration),
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the HTML page returned after submitting.
+    pub fn parse(html: &str) -> anyhow::Result<Self> {
+        let message = article_text(html).unwrap_or(html);
+
+        if message.contains("That's the right answer") {
+            Ok(Self::Correct)
+        } else if message.contains("You don't seem to be solving the right level") {
+            Ok(Self::AlreadySolved)
+        } else if message.contains("You gave an answer too recently") {
+            Ok(Self::RateLimited(parse_wait(message).unwrap_or_default()))
+        } else if message.contains("That's not the right answer") {
+            Ok(if message.contains("your answer is too high") {
+                Self::TooHigh
+            } else if message.contains("your answer is too low") {
+                Self::TooLow
+            } else {
+                Self::Wrong
+            })
+        } else {
+            anyhow::bail!("Unrecognized response: {}", message.trim())
+        }
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "too high"),
+            Self::TooLow => write!(f, "too low"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::RateLimited(wait) => write!(f, "rate limited for {}s", wait.as_secs()),
+            Self::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// The verdict is the only `<article>` on the answer page.
+fn article_text(html: &str) -> Option<&str> {
+    let start = html.find("<article")?;
+    let end = html[start..].find("</article>")?;
+
+    Some(&html[start..start + end])
+}
+
+/// Parses the wait from a message like "You have 1m 5s left to wait."
+fn parse_wait(message: &str) -> Option<Duration> {
+    let before_wait = &message[..message.find("left to wait")?];
+    let amounts = &before_wait[before_wait.rfind("You have")? + "You have".len()..];
+
+    amounts
+        .split_ascii_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+
+            match unit {
+                "h" => Some(number * 60 * 60),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+pub async fn submit_answer(
+    year: i32,
+    day: usize,
+    part: Part,
+    answer: &Answer,
+) -> anyhow::Result<Verdict> {
+    if let Answer::Grid(_) = answer {
+        anyhow::bail!("Grid answers can't be submitted, read the letters first");
+    }
+
+    let url = format!("{}/{}/day/{}/answer", input::base_url(), year, day);
+    let form = [("level", part.to_string()), ("answer", answer.to_string())];
+
+    let client = reqwest::Client::new();
+    let request = client
+        .post(url)
+        .header("Cookie", input::session_cookie()?)
+        .form(&form);
+
+    let html = input::send(request)
+        .await
+        .context("Error submitting answer")?;
+
+    Verdict::parse(&html)
+}
+
+/// Solves one part of a day and submits the answer it produces.
+pub async fn solve_and_submit(year: i32, day: usize, part: Part) -> anyhow::Result<Verdict> {
+    let day_impl = aoc::get_day(year, day)?;
+    let input_for_day = input::get_input(year, day).await?;
+
+    let outcome = PartOutcome::run(|| day_impl.solve(part, &input_for_day));
+    let answer = match outcome.result {
+        PartResult::Solved(answer) => answer,
+        other => anyhow::bail!("Part {} didn't produce an answer: {}", part, other),
+    };
+
+    let mut ledger = Ledger::load(year)?;
+    match ledger.check(day, part, &answer) {
+        Check::Unknown => {}
+        check => anyhow::bail!("Refusing to submit {}, it's {}", answer, check),
+    }
+
+    println!(
+        "Submitting {} for {} day {} part {}...",
+        answer, year, day, part
+    );
+    let verdict = submit_answer(year, day, part, &answer).await?;
+    ledger.record(day, part, &answer, &verdict)?;
+
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_wait, Verdict};
+
+    #[test]
+    fn reads_verdicts_from_answer_pages() {
+        let verdict = |page: &str| Verdict::parse(page).unwrap();
+
+        assert_eq!(
+            verdict(include_str!("../fixtures/answer/correct.html")),
+            Verdict::Correct
+        );
+        assert_eq!(
+            verdict(include_str!("../fixtures/answer/too-high.html")),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            verdict(include_str!("../fixtures/answer/too-low.html")),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            verdict(include_str!("../fixtures/answer/wrong.html")),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            verdict(include_str!("../fixtures/answer/already-solved.html")),
+            Verdict::AlreadySolved
+        );
+        assert_eq!(
+            verdict(include_str!("../fixtures/answer/rate-limited.html")),
+            Verdict::RateLimited(Duration::from_secs(4 * 60 + 25))
+        );
+        assert!(Verdict::parse("<article><p>Something else</p></article>").is_err());
+    }
+
+    #[test]
+    fn parses_waits() {
+        let wait = |message| parse_wait(message).map(|wait| wait.as_secs());
+
+        assert_eq!(wait("You have 1m 5s left to wait."), Some(65));
+        assert_eq!(wait("You have 30s left to wait."), Some(30));
+        assert_eq!(wait("You have 1h 2m 3s left to wait."), Some(3723));
+        assert_eq!(wait("Please wait a while."), None);
+    }
+}
@@ -0,0 +1,99 @@
+use std::io::Write;
+
+use anyhow::Context;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+use chrono_tz::America::New_York;
+use chrono_tz::Tz;
+
+/// Puzzles unlock at midnight in this timezone.
+pub const AOC_TIMEZONE: Tz = New_York;
+
+fn now() -> DateTime<Tz> {
+    Utc::now().with_timezone(&AOC_TIMEZONE)
+}
+
+pub fn validate_day(day: usize) -> anyhow::Result<usize> {
+    if !(1..=25).contains(&day) {
+        anyhow::bail!("Day {} is outside of 1-25", day);
+    }
+
+    Ok(day)
+}
+
+fn midnight(date: NaiveDate) -> anyhow::Result<DateTime<Tz>> {
+    AOC_TIMEZONE
+        .with_ymd_and_hms(date.year(), date.month(), date.day(), 0, 0, 0)
+        .single()
+        .with_context(|| format!("No single midnight on {} in the AoC timezone", date))
+}
+
+/// Today's puzzle in the given year, going by the date where the puzzles
+/// unlock.
+pub fn current_day(year: i32) -> anyhow::Result<usize> {
+    let today = now().date_naive();
+    if today.year() != year || today.month() != 12 || today.day() > 25 {
+        anyhow::bail!(
+            "It's {} in the AoC timezone, which isn't December 1-25 of {}, so pick a day with --day",
+            today.format("%B %-d, %Y"),
+            year
+        );
+    }
+
+    Ok(today.day() as usize)
+}
+
+/// When the given day unlocks, or the next day to unlock if none is given.
+pub fn unlock_time(year: i32, day: Option<usize>) -> anyhow::Result<(usize, DateTime<Tz>)> {
+    match day {
+        Some(day) => {
+            let date = NaiveDate::from_ymd_opt(year, 12, validate_day(day)? as u32)
+                .with_context(|| format!("Invalid year {}", year))?;
+            let unlock = midnight(date)?;
+
+            Ok((day, unlock))
+        }
+        None => {
+            let unlock = midnight(now().date_naive() + Duration::days(1))?;
+            if unlock.year() != year || unlock.month() != 12 || unlock.day() > 25 {
+                anyhow::bail!(
+                    "No {} puzzle unlocks at the next midnight ({}), pick a day with --day",
+                    year,
+                    unlock.format("%Y-%m-%d")
+                );
+            }
+
+            Ok((unlock.day() as usize, unlock))
+        }
+    }
+}
+
+/// Shows a countdown until the day unlocks, returning the day once it has.
+pub async fn wait_for_unlock(year: i32, day: Option<usize>) -> anyhow::Result<usize> {
+    let (day, unlock) = unlock_time(year, day)?;
+
+    loop {
+        let remaining = unlock.signed_duration_since(now());
+        if remaining <= Duration::zero() {
+            eprintln!("\rDay {} is unlocked!{:20}", day, "");
+            return Ok(day);
+        }
+
+        let seconds = remaining.num_seconds();
+        eprint!(
+            "\rDay {} unlocks in {:02}:{:02}:{:02}",
+            day,
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        );
+        std::io::stderr().flush().ok();
+
+        let until_next_second = remaining.num_milliseconds() % 1000;
+        let sleep_millis = if until_next_second == 0 {
+            1000
+        } else {
+            until_next_second
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(sleep_millis as u64)).await;
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::answer::Answer;
+use crate::aoc::Part;
+use crate::config;
+use crate::runner::{self, DayOutcome, DaySelection, PartResult};
+use crate::sandbox::Limits;
+
+/// The known-good answers for each day, keyed by year and day number:
+///
+/// ```toml
+/// [2021.1]
+/// part1 = 1387
+/// part2 = 1362
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct ExpectedAnswers(BTreeMap<String, BTreeMap<String, ExpectedDay>>);
+
+/// The known-good answers for a single day.
+#[derive(Debug, Default, Deserialize)]
+pub struct ExpectedDay {
+    part1: Option<toml::Value>,
+    part2: Option<toml::Value>,
+}
+
+impl ExpectedDay {
+    pub fn get(&self, part: Part) -> anyhow::Result<Option<Answer>> {
+        let value = match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        };
+
+        match value {
+            None => Ok(None),
+            Some(toml::Value::Integer(value)) => Ok(Some((*value).into())),
+            Some(toml::Value::String(value)) => value.parse().map(Some),
+            Some(other) => anyhow::bail!(
+                "Expected answer for part {} must be an integer or string, found {}",
+                part,
+                other
+            ),
+        }
+    }
+}
+
+impl ExpectedAnswers {
+    /// Answers differ between accounts, so each profile has its own
+    /// `answers.<profile>.toml`.
+    pub fn path() -> String {
+        match &config::get().profile {
+            Some(profile) => format!("answers.{}.toml", profile),
+            None => "answers.toml".to_owned(),
+        }
+    }
+
+    pub fn load() -> anyhow::Result<Self> {
+        let path = Self::path();
+        let contents = read_to_string(&path)
+            .with_context(|| format!("Failed to read expected answers from {}", path))?;
+
+        toml::from_str(&contents).context("Failed to parse expected answers")
+    }
+
+    pub fn has_day(&self, year: i32, day: usize) -> bool {
+        self.0
+            .get(&year.to_string())
+            .map_or(false, |days| days.contains_key(&day.to_string()))
+    }
+
+    pub fn get(&self, year: i32, day: usize, part: Part) -> anyhow::Result<Option<Answer>> {
+        let expected = self
+            .0
+            .get(&year.to_string())
+            .and_then(|days| days.get(&day.to_string()));
+
+        match expected {
+            Some(expected) => expected
+                .get(part)
+                .with_context(|| format!("Invalid expected answer for {} day {}", year, day)),
+            None => Ok(None),
+        }
+    }
+}
+
+pub enum Verification {
+    Pass,
+    Fail { expected: Answer, actual: String },
+    Missing,
+}
+
+/// Runs each selected day that has expected answers for the profile and
+/// checks them, failing if any of them don't match.
+pub async fn verify(year: i32, selection: &DaySelection, limits: &Limits) -> anyhow::Result<()> {
+    let expected = ExpectedAnswers::load()?;
+    let selection = DaySelection(
+        selection
+            .0
+            .iter()
+            .copied()
+            .filter(|&day| expected.has_day(year, day))
+            .collect(),
+    );
+    if selection.0.is_empty() {
+        anyhow::bail!(
+            "No expected answers for the selected days in {}",
+            ExpectedAnswers::path()
+        );
+    }
+
+    let outcomes = runner::run_days(year, &selection, limits).await;
+
+    let mut failures = 0;
+    for outcome in &outcomes {
+        for part in Part::BOTH {
+            let verification = verify_part(&expected, year, outcome, part)?;
+            print_verification(outcome.day, part, &verification);
+
+            if let Verification::Fail { .. } = verification {
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        anyhow::bail!("{} answer(s) didn't match", failures);
+    }
+
+    Ok(())
+}
+
+fn verify_part(
+    expected: &ExpectedAnswers,
+    year: i32,
+    outcome: &DayOutcome,
+    part: Part,
+) -> anyhow::Result<Verification> {
+    let expected = match expected.get(year, outcome.day, part)? {
+        Some(expected) => expected,
+        None => return Ok(Verification::Missing),
+    };
+
+    let actual = match &outcome.parts {
+        Err(error) => format!("error: {:#}", error),
+        Ok(parts) => match &parts[part.number() - 1].result {
+            PartResult::Solved(answer) if answer == &expected => return Ok(Verification::Pass),
+            PartResult::Solved(answer) => answer.to_string(),
+            other => other.to_string(),
+        },
+    };
+
+    Ok(Verification::Fail { expected, actual })
+}
+
+fn print_verification(day: usize, part: Part, verification: &Verification) {
+    let label = format!("Day {} part {}", day, part);
+
+    match verification {
+        Verification::Pass => println!("{}: pass", label),
+        Verification::Missing => println!("{}: missing expected answer", label),
+        Verification::Fail { expected, actual } => {
+            println!("{}: FAIL", label);
+            for line in expected.to_string().lines() {
+                println!("  - {}", line);
+            }
+            for line in actual.lines() {
+                println!("  + {}", line);
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::answer::Answer;
+use crate::aoc::{Example, Solution};
+use anyhow::Context;
+
+pub struct Day1;
+
+crate::aoc::register_day! {
+    year: 2021,
+    day: 1,
+    title: "Sonar Sweep",
+    parts: [One, Two],
+    solution: Day1,
+}
+
+fn parse_depths(input: &str) -> anyhow::Result<Vec<usize>> {
+    input
+        .lines()
+        .map(|line| line.parse().context("invalid depth"))
+        .collect()
+}
+
+const EXAMPLE: &str = "\
+199
+200
+208
+210
+200
+207
+240
+269
+260
+263
+";
+
+impl Solution for Day1 {
+    type Parsed<'a> = Vec<usize>;
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part1: Some("7"),
+            part2: Some("5"),
+        }]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        parse_depths(input)
+    }
+
+    fn part1(&self, depths: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        Ok(depths
+            .windows(2)
+            .filter(|window| window[0] < window[1])
+            .count()
+            .into())
+    }
+
+    fn part2(&self, depths: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        let sums_of_three_wide_windows = depths
+            .windows(3)
+            .map(|window| window.iter().sum())
+            .collect::<Vec<usize>>();
+
+        Ok(sums_of_three_wide_windows
+            .windows(2)
+            .filter(|window| window[0] < window[1])
+            .count()
+            .into())
+    }
+}
+
+crate::aoc::example_tests!(Day1);
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
-use crate::aoc::Aoc;
+use crate::answer::Answer;
+use crate::aoc::{Aoc, Example};
 
 fn validate_chunk(chunk: &str) -> Result<Vec<char>, char> {
     let chunk_ends: HashMap<char, char> = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')]
@@ -27,8 +28,37 @@ fn validate_chunk(chunk: &str) -> Result<Vec<char>, char> {
 
 pub struct Day10;
 
+crate::aoc::register_day! {
+    year: 2021,
+    day: 10,
+    title: "Syntax Scoring",
+    parts: [One, Two],
+    solution: Day10,
+}
+
+const EXAMPLE: &str = "\
+[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]
+";
+
 impl Aoc for Day10 {
-    fn part1(&self, input: &str) -> anyhow::Result<usize> {
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part1: Some("26397"),
+            part2: Some("288957"),
+        }]
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<Answer> {
         let bracket_scores: HashMap<char, usize> = [(')', 3), (']', 57), ('}', 1197), ('>', 25137)]
             .into_iter()
             .collect();
@@ -42,10 +72,11 @@ impl Aoc for Day10 {
                     .and_then(|closer| bracket_scores.get(&closer).cloned())
                     .unwrap_or_default()
             })
-            .sum())
+            .sum::<usize>()
+            .into())
     }
 
-    fn part2(&self, input: &str) -> anyhow::Result<usize> {
+    fn part2(&self, input: &str) -> anyhow::Result<Answer> {
         let bracket_scores: HashMap<char, usize> = [(')', 1), (']', 2), ('}', 3), ('>', 4)]
             .into_iter()
             .collect();
@@ -66,6 +97,8 @@ impl Aoc for Day10 {
             .collect();
         scores.sort();
 
-        Ok(scores[(scores.len() - 1) / 2])
+        Ok(scores[(scores.len() - 1) / 2].into())
     }
 }
+
+crate::aoc::example_tests!(Day10);
@@ -1,11 +1,12 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::HashSet;
 
 use anyhow::Context;
 
-use crate::aoc::Aoc;
+use crate::answer::Answer;
+use crate::aoc::{Example, Solution};
 
-#[derive(Debug)]
-struct OctopusField {
+#[derive(Debug, Clone)]
+pub struct OctopusField {
     grid: Vec<Vec<usize>>,
     width: usize,
     height: usize,
@@ -129,17 +130,15 @@ impl OctopusField {
         let mut flashed = flashing.iter().cloned().collect::<HashSet<_>>();
 
         while let Some(current) = flashing.pop() {
-            flashed.insert(current.clone());
-
             for neighbor in self.neighbors(&current) {
                 let new_energy = self.increase_energy(&neighbor, 1);
-                if new_energy > 9 && !flashed.contains(&neighbor) {
+                if new_energy > 9 && flashed.insert(neighbor.clone()) {
                     flashing.push(neighbor);
                 }
             }
         }
 
-        flashed.iter().for_each(|pos| self.set_energy(&pos, 0));
+        flashed.iter().for_each(|pos| self.set_energy(pos, 0));
 
         flashed.len()
     }
@@ -166,21 +165,52 @@ impl std::fmt::Display for OctopusField {
 
 pub struct Day11;
 
-impl Aoc for Day11 {
-    fn part1(&self, input: &str) -> anyhow::Result<usize> {
-        let mut field = OctopusField::parse(input)?;
+crate::aoc::register_day! {
+    year: 2021,
+    day: 11,
+    title: "Dumbo Octopus",
+    parts: [One],
+    solution: Day11,
+}
+
+const EXAMPLE: &str = "\
+5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526
+";
+
+impl Solution for Day11 {
+    type Parsed<'a> = OctopusField;
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part1: Some("1656"),
+            part2: None,
+        }]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        OctopusField::parse(input)
+    }
+
+    fn part1(&self, field: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        let mut field = field.clone();
         let mut total_flashes = 0;
 
-        println!("{}\n", field);
-        for _ in 0..10 {
+        for _ in 0..100 {
             total_flashes += field.step();
-            println!("{}\n", field);
         }
 
-        Ok(total_flashes)
-    }
-
-    fn part2(&self, _input: &str) -> anyhow::Result<usize> {
-        unimplemented!()
+        Ok(total_flashes.into())
     }
 }
+
+crate::aoc::example_tests!(Day11);
@@ -2,10 +2,11 @@ use std::collections::HashMap;
 
 use anyhow::Context;
 
-use crate::aoc::Aoc;
+use crate::answer::Answer;
+use crate::aoc::{Example, Solution};
 
 #[derive(Debug)]
-struct CaveSystem<'s> {
+pub struct CaveSystem<'s> {
     edges: HashMap<&'s str, Vec<&'s str>>,
 }
 
@@ -20,7 +21,7 @@ impl<'s> CaveSystem<'s> {
         let adjacencies: Vec<(&str, &str)> = input
             .trim()
             .lines()
-            .map(|line| line.split_once("-").context("Invalid node pair"))
+            .map(|line| line.split_once('-').context("Invalid node pair"))
             .collect::<anyhow::Result<_>>()?;
 
         let mut edges = HashMap::<&str, Vec<&str>>::new();
@@ -32,7 +33,7 @@ impl<'s> CaveSystem<'s> {
         Ok(Self { edges })
     }
 
-    fn visible_caves<'cs: 's>(&'cs self, from: &'s str) -> impl Iterator<Item = &'s str> + 'cs {
+    fn visible_caves<'cs>(&'cs self, from: &str) -> impl Iterator<Item = &'s str> + 'cs {
         self.edges
             .get(from)
             .into_iter()
@@ -40,7 +41,7 @@ impl<'s> CaveSystem<'s> {
             .filter(|cave| cave != &"start")
     }
 
-    fn all_paths<'cs: 's>(&'cs self, path: Path<'s>, allow_repeat: bool) -> Vec<Path<'s>> {
+    fn all_paths(&self, path: Path<'s>, allow_repeat: bool) -> Vec<Path<'s>> {
         match path.caves.last().cloned() {
             None => self.all_paths(Path::single("start"), allow_repeat),
             Some(last_cave) if last_cave != "end" => self
@@ -49,7 +50,7 @@ impl<'s> CaveSystem<'s> {
                     let lowercase = visible_cave.chars().all(char::is_lowercase);
                     let seen = path.caves.contains(&visible_cave);
 
-                    if lowercase && seen && !(allow_repeat && !path.has_repeat) {
+                    if lowercase && seen && (!allow_repeat || path.has_repeat) {
                         vec![]
                     } else {
                         self.all_paths(path.join(visible_cave), allow_repeat)
@@ -94,18 +95,50 @@ impl<'s> Path<'s> {
 
 pub struct Day12;
 
-impl Aoc for Day12 {
-    fn part1(&self, input: &str) -> anyhow::Result<usize> {
-        let system = CaveSystem::parse(input)?;
+crate::aoc::register_day! {
+    year: 2021,
+    day: 12,
+    title: "Passage Pathing",
+    parts: [One, Two],
+    solution: Day12,
+}
+
+const EXAMPLE: &str = "\
+start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end
+";
+
+impl Solution for Day12 {
+    type Parsed<'a> = CaveSystem<'a>;
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part1: Some("10"),
+            part2: Some("36"),
+        }]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        CaveSystem::parse(input)
+    }
+
+    fn part1(&self, system: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
         let paths = system.all_paths(Path::empty(), false);
 
-        Ok(paths.len())
+        Ok(paths.len().into())
     }
 
-    fn part2(&self, input: &str) -> anyhow::Result<usize> {
-        let system = CaveSystem::parse(input)?;
+    fn part2(&self, system: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
         let paths = system.all_paths(Path::empty(), true);
 
-        Ok(paths.len())
+        Ok(paths.len().into())
     }
 }
+
+crate::aoc::example_tests!(Day12);
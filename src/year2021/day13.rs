@@ -2,9 +2,12 @@ use std::collections::HashSet;
 
 use anyhow::Context;
 
-use crate::aoc::Aoc;
+use crate::answer::Answer;
+use crate::aoc::{Example, Solution};
+use crate::ocr;
 
-struct Paper {
+#[derive(Clone)]
+pub struct Paper {
     points: HashSet<Point>,
 }
 
@@ -36,7 +39,7 @@ impl Paper {
     }
 }
 
-#[derive(PartialEq, Eq, Hash)]
+#[derive(PartialEq, Eq, Hash, Clone)]
 struct Point {
     x: usize,
     y: usize,
@@ -45,7 +48,7 @@ struct Point {
 impl Point {
     fn parse(point: &str) -> anyhow::Result<Self> {
         let (x, y) = point
-            .split_once(",")
+            .split_once(',')
             .context("Couldn't split coordinates")?;
 
         Ok(Self {
@@ -55,14 +58,14 @@ impl Point {
     }
 }
 
-struct Instruction {
+pub struct Instruction {
     line: usize,
     axis: Axis,
 }
 
 impl Instruction {
     fn parse(instruction: &str) -> anyhow::Result<Self> {
-        let (start, line) = instruction.split_once("=").context("Missing equals")?;
+        let (start, line) = instruction.split_once('=').context("Missing equals")?;
         let axis = match start.chars().last() {
             Some('x') => Axis::X,
             Some('y') => Axis::Y,
@@ -100,32 +103,83 @@ fn parse_input(input: &str) -> anyhow::Result<(Paper, Vec<Instruction>)> {
 
 pub struct Day13;
 
-impl Aoc for Day13 {
-    fn part1(&self, input: &str) -> anyhow::Result<usize> {
-        let (mut paper, instructions) = parse_input(input)?;
+crate::aoc::register_day! {
+    year: 2021,
+    day: 13,
+    title: "Transparent Origami",
+    parts: [One, Two],
+    solution: Day13,
+}
+
+const EXAMPLE: &str = "\
+6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5
+";
+
+impl Solution for Day13 {
+    type Parsed<'a> = (Paper, Vec<Instruction>);
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part1: Some("17"),
+            part2: None,
+        }]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        parse_input(input)
+    }
+
+    fn part1(&self, (paper, instructions): &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        let mut paper = paper.clone();
 
         instructions
-            .into_iter()
+            .iter()
             .take(1)
-            .for_each(|instruction| paper.fold(&instruction));
+            .for_each(|instruction| paper.fold(instruction));
 
-        Ok(paper.points.len())
+        Ok(paper.points.len().into())
     }
 
-    fn part2(&self, input: &str) -> anyhow::Result<usize> {
-        let (mut paper, instructions) = parse_input(input)?;
+    fn part2(&self, (paper, instructions): &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        let mut paper = paper.clone();
 
         instructions
-            .into_iter()
-            .for_each(|instruction| paper.fold(&instruction));
+            .iter()
+            .for_each(|instruction| paper.fold(instruction));
+
+        if let Ok(code) = ocr::recognize_points(paper.points.iter().map(|point| (point.x, point.y)))
+        {
+            return Ok(code.into());
+        }
 
+        // Fall back to the raw drawing when it isn't made of letters
         let (max_x, max_y) = paper.points.iter().fold((0, 0), |(max_x, max_y), point| {
             (std::cmp::max(max_x, point.x), std::cmp::max(max_y, point.y))
         });
 
-        for y in 0..=max_y {
-            println!(
-                "{}",
+        let rows = (0..=max_y)
+            .map(|y| {
                 (0..=max_x)
                     .map(|x| {
                         if paper.points.contains(&Point { x, y }) {
@@ -135,9 +189,11 @@ impl Aoc for Day13 {
                         }
                     })
                     .collect::<String>()
-            );
-        }
+            })
+            .collect::<Vec<String>>();
 
-        Ok(0)
+        Ok(rows.into())
     }
 }
+
+crate::aoc::example_tests!(Day13);
@@ -2,9 +2,10 @@ use std::collections::HashMap;
 
 use anyhow::Context;
 
-use crate::aoc::Aoc;
+use crate::answer::Answer;
+use crate::aoc::{Example, Solution};
 
-struct Rule {
+pub struct Rule {
     start: char,
     end: char,
     insert: char,
@@ -22,7 +23,8 @@ impl Rule {
     }
 }
 
-struct Polymer {
+#[derive(Clone)]
+pub struct Polymer {
     links: HashMap<char, HashMap<char, usize>>,
 }
 
@@ -112,24 +114,69 @@ fn parse_input(input: &str) -> anyhow::Result<(Polymer, Vec<Rule>)> {
 
 pub struct Day14;
 
-impl Aoc for Day14 {
-    fn part1(&self, input: &str) -> anyhow::Result<usize> {
-        let (mut polymer, rules) = parse_input(input)?;
+crate::aoc::register_day! {
+    year: 2021,
+    day: 14,
+    title: "Extended Polymerization",
+    parts: [One, Two],
+    solution: Day14,
+}
+
+const EXAMPLE: &str = "\
+NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C
+";
+
+impl Solution for Day14 {
+    type Parsed<'a> = (Polymer, Vec<Rule>);
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part1: Some("1588"),
+            part2: Some("2188189693529"),
+        }]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        parse_input(input)
+    }
+
+    fn part1(&self, (polymer, rules): &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        let mut polymer = polymer.clone();
 
         for _ in 0..10 {
             polymer = polymer.grow(&rules[..]);
         }
 
-        polymer.count_gap()
+        polymer.count_gap().map(Answer::from)
     }
 
-    fn part2(&self, input: &str) -> anyhow::Result<usize> {
-        let (mut polymer, rules) = parse_input(input)?;
+    fn part2(&self, (polymer, rules): &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        let mut polymer = polymer.clone();
 
         for _ in 0..40 {
             polymer = polymer.grow(&rules[..]);
         }
 
-        polymer.count_gap()
+        polymer.count_gap().map(Answer::from)
     }
 }
+
+crate::aoc::example_tests!(Day14);
@@ -0,0 +1,13 @@
+use crate::aoc::Aoc;
+
+pub struct Day15;
+
+crate::aoc::register_day! {
+    year: 2021,
+    day: 15,
+    title: "Chiton",
+    parts: [],
+    solution: Day15,
+}
+
+impl Aoc for Day15 {}
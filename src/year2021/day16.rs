@@ -3,10 +3,11 @@ use std::num::ParseIntError;
 use anyhow::Context;
 use nom::{bytes::streaming::take, combinator::map_res, IResult};
 
-use crate::aoc::Aoc;
+use crate::answer::Answer;
+use crate::aoc::{Example, Solution};
 
 #[derive(Debug)]
-struct Packet {
+pub struct Packet {
     version: usize,
     content: PacketType,
 }
@@ -40,7 +41,7 @@ fn parse_packet(input: &str) -> IResult<&str, Packet> {
         let input = if length_type_id == 0 {
             let (input, length) = parse_binary(input, 15)?;
             let (input, mut subpacket_bits) = take(length)(input)?;
-            while subpacket_bits.len() > 0 {
+            while !subpacket_bits.is_empty() {
                 let (remaining, subpacket) = parse_packet(subpacket_bits)?;
                 subpackets.push(subpacket);
                 subpacket_bits = remaining;
@@ -126,8 +127,8 @@ fn total_of_all_version_numbers(packets: &[Packet]) -> usize {
 }
 
 fn first_two_values(packet: &[Packet]) -> anyhow::Result<(usize, usize)> {
-    let first = packet.iter().nth(0).context("Missing first subvalue")?;
-    let second = packet.iter().nth(1).context("Missing second subvalue")?;
+    let first = packet.first().context("Missing first subvalue")?;
+    let second = packet.get(1).context("Missing second subvalue")?;
 
     Ok((process_packet(first)?, process_packet(second)?))
 }
@@ -169,16 +170,93 @@ fn process_packet(packet: &Packet) -> anyhow::Result<usize> {
 
 pub struct Day16;
 
-impl Aoc for Day16 {
-    fn part1(&self, input: &str) -> anyhow::Result<usize> {
-        let packet = parse_input(input)?;
+crate::aoc::register_day! {
+    year: 2021,
+    day: 16,
+    title: "Packet Decoder",
+    parts: [One, Two],
+    solution: Day16,
+}
+
+impl Solution for Day16 {
+    type Parsed<'a> = Packet;
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                input: "8A004A801A8002F478",
+                part1: Some("16"),
+                part2: None,
+            },
+            Example {
+                input: "620080001611562C8802118E34",
+                part1: Some("12"),
+                part2: None,
+            },
+            Example {
+                input: "C0015000016115A2E0802F182340",
+                part1: Some("23"),
+                part2: None,
+            },
+            Example {
+                input: "A0016C880162017C3686B18A3D4780",
+                part1: Some("31"),
+                part2: None,
+            },
+            Example {
+                input: "C200B40A82",
+                part1: None,
+                part2: Some("3"),
+            },
+            Example {
+                input: "04005AC33890",
+                part1: None,
+                part2: Some("54"),
+            },
+            Example {
+                input: "880086C3E88112",
+                part1: None,
+                part2: Some("7"),
+            },
+            Example {
+                input: "CE00C43D881120",
+                part1: None,
+                part2: Some("9"),
+            },
+            Example {
+                input: "D8005AC2A8F0",
+                part1: None,
+                part2: Some("1"),
+            },
+            Example {
+                input: "F600BC2D8F",
+                part1: None,
+                part2: Some("0"),
+            },
+            Example {
+                input: "9C005AC2F8F0",
+                part1: None,
+                part2: Some("0"),
+            },
+            Example {
+                input: "9C0141080250320F1802104A08",
+                part1: None,
+                part2: Some("1"),
+            },
+        ]
+    }
 
-        Ok(total_of_all_version_numbers(&[packet]))
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        parse_input(input)
     }
 
-    fn part2(&self, input: &str) -> anyhow::Result<usize> {
-        let packet = parse_input(input)?;
+    fn part1(&self, packet: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        Ok(total_of_all_version_numbers(std::slice::from_ref(packet)).into())
+    }
 
-        process_packet(&packet)
+    fn part2(&self, packet: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        process_packet(packet).map(Answer::from)
     }
 }
+
+crate::aoc::example_tests!(Day16);
@@ -0,0 +1,13 @@
+use crate::aoc::Aoc;
+
+pub struct Day17;
+
+crate::aoc::register_day! {
+    year: 2021,
+    day: 17,
+    title: "Trick Shot",
+    parts: [],
+    solution: Day17,
+}
+
+impl Aoc for Day17 {}
@@ -5,6 +5,7 @@ use nom::combinator::{map, map_res};
 use nom::sequence::{delimited, pair};
 use nom::IResult;
 
+use crate::answer::Answer;
 use crate::aoc::Aoc;
 
 struct SnailfishNumber {
@@ -12,11 +13,6 @@ struct SnailfishNumber {
     right: SnailfishNumberType,
 }
 
-enum Side {
-    Left,
-    Right,
-}
-
 enum SnailfishNumberType {
     Literal(usize),
     Nested(Box<SnailfishNumber>),
@@ -28,7 +24,7 @@ impl SnailfishNumber {
     }
 
     pub fn reduce(self) -> Self {
-        3 * self.lef1
+        todo!("reducing snailfish numbers")
     }
 
     // pub fn reduce_inner(self, level: usize) -> Self {
@@ -86,8 +82,16 @@ fn parse_snailfish_number_type(input: &str) -> IResult<&str, SnailfishNumberType
 
 pub struct Day18;
 
+crate::aoc::register_day! {
+    year: 2021,
+    day: 18,
+    title: "Snailfish",
+    parts: [],
+    solution: Day18,
+}
+
 impl Aoc for Day18 {
-    fn part1(&self, input: &str) -> anyhow::Result<usize> {
+    fn part1(&self, input: &str) -> anyhow::Result<Answer> {
         let mut numbers: Vec<SnailfishNumber> = input
             .trim()
             .lines()
@@ -103,10 +107,6 @@ impl Aoc for Day18 {
             base_number = base_number + number;
         }
 
-        Ok(base_number.magnitude())
-    }
-
-    fn part2(&self, _input: &str) -> anyhow::Result<usize> {
-        unimplemented!()
+        Ok(base_number.magnitude().into())
     }
 }
@@ -0,0 +1,13 @@
+use crate::aoc::Aoc;
+
+pub struct Day19;
+
+crate::aoc::register_day! {
+    year: 2021,
+    day: 19,
+    title: "Beacon Scanner",
+    parts: [],
+    solution: Day19,
+}
+
+impl Aoc for Day19 {}
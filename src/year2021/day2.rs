@@ -1,4 +1,5 @@
-use crate::aoc::Aoc;
+use crate::answer::Answer;
+use crate::aoc::{Aoc, Example};
 use anyhow::Context;
 use std::str::FromStr;
 
@@ -11,7 +12,7 @@ impl FromStr for Command {
     type Err = anyhow::Error;
 
     fn from_str(command: &str) -> anyhow::Result<Self> {
-        let mut part_iter = command.split(" ");
+        let mut part_iter = command.split(' ');
         let direction = Direction::from_str(
             part_iter
                 .next()
@@ -51,8 +52,33 @@ impl FromStr for Direction {
 
 pub struct Day2;
 
+crate::aoc::register_day! {
+    year: 2021,
+    day: 2,
+    title: "Dive!",
+    parts: [One, Two],
+    solution: Day2,
+}
+
+const EXAMPLE: &str = "\
+forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2
+";
+
 impl Aoc for Day2 {
-    fn part1(&self, input: &str) -> anyhow::Result<usize> {
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part1: Some("150"),
+            part2: Some("900"),
+        }]
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<Answer> {
         let commands = input
             .lines()
             .map(Command::from_str)
@@ -75,10 +101,10 @@ impl Aoc for Day2 {
             }
         }
 
-        Ok(position * depth)
+        Ok((position * depth).into())
     }
 
-    fn part2(&self, input: &str) -> anyhow::Result<usize> {
+    fn part2(&self, input: &str) -> anyhow::Result<Answer> {
         let commands = input
             .lines()
             .map(Command::from_str)
@@ -103,6 +129,8 @@ impl Aoc for Day2 {
             }
         }
 
-        Ok(position * depth)
+        Ok((position * depth).into())
     }
 }
+
+crate::aoc::example_tests!(Day2);
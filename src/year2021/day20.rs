@@ -0,0 +1,13 @@
+use crate::aoc::Aoc;
+
+pub struct Day20;
+
+crate::aoc::register_day! {
+    year: 2021,
+    day: 20,
+    title: "Trench Map",
+    parts: [],
+    solution: Day20,
+}
+
+impl Aoc for Day20 {}
@@ -0,0 +1,13 @@
+use crate::aoc::Aoc;
+
+pub struct Day21;
+
+crate::aoc::register_day! {
+    year: 2021,
+    day: 21,
+    title: "Dirac Dice",
+    parts: [],
+    solution: Day21,
+}
+
+impl Aoc for Day21 {}
@@ -0,0 +1,13 @@
+use crate::aoc::Aoc;
+
+pub struct Day22;
+
+crate::aoc::register_day! {
+    year: 2021,
+    day: 22,
+    title: "Reactor Reboot",
+    parts: [],
+    solution: Day22,
+}
+
+impl Aoc for Day22 {}
@@ -0,0 +1,13 @@
+use crate::aoc::Aoc;
+
+pub struct Day23;
+
+crate::aoc::register_day! {
+    year: 2021,
+    day: 23,
+    title: "Amphipod",
+    parts: [],
+    solution: Day23,
+}
+
+impl Aoc for Day23 {}
@@ -0,0 +1,13 @@
+use crate::aoc::Aoc;
+
+pub struct Day24;
+
+crate::aoc::register_day! {
+    year: 2021,
+    day: 24,
+    title: "Arithmetic Logic Unit",
+    parts: [],
+    solution: Day24,
+}
+
+impl Aoc for Day24 {}
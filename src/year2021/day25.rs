@@ -0,0 +1,13 @@
+use crate::aoc::Aoc;
+
+pub struct Day25;
+
+crate::aoc::register_day! {
+    year: 2021,
+    day: 25,
+    title: "Sea Cucumber",
+    parts: [],
+    solution: Day25,
+}
+
+impl Aoc for Day25 {}
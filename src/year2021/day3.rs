@@ -1,4 +1,5 @@
-use crate::aoc::Aoc;
+use crate::answer::Answer;
+use crate::aoc::{Aoc, Example};
 use anyhow::Context;
 
 fn parse_binary(number: &str) -> anyhow::Result<usize> {
@@ -7,8 +8,39 @@ fn parse_binary(number: &str) -> anyhow::Result<usize> {
 
 pub struct Day3;
 
+crate::aoc::register_day! {
+    year: 2021,
+    day: 3,
+    title: "Binary Diagnostic",
+    parts: [One, Two],
+    solution: Day3,
+}
+
+const EXAMPLE: &str = "\
+00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010
+";
+
 impl Aoc for Day3 {
-    fn part1(&self, input: &str) -> anyhow::Result<usize> {
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part1: Some("198"),
+            part2: Some("230"),
+        }]
+    }
+
+    fn part1(&self, input: &str) -> anyhow::Result<Answer> {
         let numbers = input.lines().collect::<Vec<_>>();
 
         let bit_count = numbers[0].len();
@@ -31,10 +63,10 @@ impl Aoc for Day3 {
             .map(|count| if count * 2 <= numbers.len() { '1' } else { '0' })
             .collect();
 
-        Ok(parse_binary(&gamma_rate)? * parse_binary(&epsilon_rate)?)
+        Ok((parse_binary(&gamma_rate)? * parse_binary(&epsilon_rate)?).into())
     }
 
-    fn part2(&self, input: &str) -> anyhow::Result<usize> {
+    fn part2(&self, input: &str) -> anyhow::Result<Answer> {
         let numbers = input.lines().collect::<Vec<_>>();
 
         let mut oxygen_rating_candidates = numbers.clone();
@@ -81,6 +113,8 @@ impl Aoc for Day3 {
         }
         let carbon_dioxide_rating = carbon_dioxide_rating_candidates[0];
 
-        Ok(parse_binary(oxygen_rating)? * parse_binary(carbon_dioxide_rating)?)
+        Ok((parse_binary(oxygen_rating)? * parse_binary(carbon_dioxide_rating)?).into())
     }
 }
+
+crate::aoc::example_tests!(Day3);
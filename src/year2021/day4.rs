@@ -1,10 +1,13 @@
-use crate::aoc::Aoc;
+use crate::answer::Answer;
+use crate::aoc::{Example, Solution};
 use anyhow::Context;
 
-struct Board {
+#[derive(Clone)]
+pub struct Board {
     squares: Vec<Vec<Square>>,
 }
 
+#[derive(Clone)]
 struct Square {
     value: usize,
     seen: bool,
@@ -54,7 +57,7 @@ impl Board {
             return true;
         }
 
-        return false;
+        false
     }
 
     fn score(&self, drawing: usize) -> usize {
@@ -80,7 +83,7 @@ fn parse_bingo(input: &str) -> anyhow::Result<(Vec<usize>, Vec<Board>)> {
     let drawings = lines
         .next()
         .ok_or_else(|| anyhow::anyhow!("No drawings for bingo"))?
-        .split(",")
+        .split(',')
         .map(|number| number.parse().context("Invalid drawing"))
         .collect::<anyhow::Result<Vec<usize>>>()?;
 
@@ -95,9 +98,53 @@ fn parse_bingo(input: &str) -> anyhow::Result<(Vec<usize>, Vec<Board>)> {
 
 pub struct Day4;
 
-impl Aoc for Day4 {
-    fn part1(&self, input: &str) -> anyhow::Result<usize> {
-        let (drawings, mut boards) = parse_bingo(input)?;
+crate::aoc::register_day! {
+    year: 2021,
+    day: 4,
+    title: "Giant Squid",
+    parts: [One, Two],
+    solution: Day4,
+}
+
+const EXAMPLE: &str = "\
+7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7
+";
+
+impl Solution for Day4 {
+    type Parsed<'a> = (Vec<usize>, Vec<Board>);
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part1: Some("4512"),
+            part2: Some("1924"),
+        }]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        parse_bingo(input)
+    }
+
+    fn part1(&self, (drawings, boards): &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        let mut boards = boards.clone();
         let mut drawing_index = 0;
 
         loop {
@@ -105,19 +152,19 @@ impl Aoc for Day4 {
             boards.iter_mut().for_each(|board| board.visit(drawing));
 
             if let Some(winner) = boards.iter().find(|board| board.has_won()) {
-                return Ok(winner.score(drawing));
+                return Ok(winner.score(drawing).into());
             }
 
             drawing_index += 1;
         }
     }
 
-    fn part2(&self, input: &str) -> anyhow::Result<usize> {
-        let (drawings, mut boards) = parse_bingo(input)?;
+    fn part2(&self, (drawings, boards): &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        let mut boards = boards.clone();
         let mut drawing_index = 0;
         let mut winning_score = 0;
 
-        while boards.len() > 0 && drawing_index < drawings.len() {
+        while !boards.is_empty() && drawing_index < drawings.len() {
             let drawing = drawings[drawing_index];
             boards.iter_mut().for_each(|board| board.visit(drawing));
 
@@ -129,6 +176,8 @@ impl Aoc for Day4 {
             drawing_index += 1;
         }
 
-        Ok(winning_score)
+        Ok(winning_score.into())
     }
 }
+
+crate::aoc::example_tests!(Day4);
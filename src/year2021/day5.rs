@@ -2,10 +2,11 @@ use std::collections::HashMap;
 
 use nom::{bytes::complete::tag, character::complete::u32 as parse_u32, IResult};
 
-use crate::aoc::Aoc;
+use crate::answer::Answer;
+use crate::aoc::{Example, Solution};
 
 #[derive(Debug)]
-struct LineSegment {
+pub struct LineSegment {
     start: Point,
     end: Point,
 }
@@ -97,9 +98,43 @@ fn points_for_line(line: &LineSegment) -> Box<dyn Iterator<Item = Point>> {
 
 pub struct Day5;
 
-impl Aoc for Day5 {
-    fn part1(&self, input: &str) -> anyhow::Result<usize> {
-        let lines = parse_line_segments(input)?;
+crate::aoc::register_day! {
+    year: 2021,
+    day: 5,
+    title: "Hydrothermal Venture",
+    parts: [One, Two],
+    solution: Day5,
+}
+
+const EXAMPLE: &str = "\
+0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2
+";
+
+impl Solution for Day5 {
+    type Parsed<'a> = Vec<LineSegment>;
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part1: Some("5"),
+            part2: Some("12"),
+        }]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        parse_line_segments(input)
+    }
+
+    fn part1(&self, lines: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
         let mut grid: HashMap<Point, usize> = HashMap::new();
 
         lines
@@ -112,11 +147,10 @@ impl Aoc for Day5 {
 
         let intersection_count = grid.values().filter(|&&count| count > 1).count();
 
-        Ok(intersection_count)
+        Ok(intersection_count.into())
     }
 
-    fn part2(&self, input: &str) -> anyhow::Result<usize> {
-        let lines = parse_line_segments(input)?;
+    fn part2(&self, lines: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
         let mut grid: HashMap<Point, usize> = HashMap::new();
 
         lines.iter().flat_map(points_for_line).for_each(|point| {
@@ -125,6 +159,8 @@ impl Aoc for Day5 {
 
         let intersection_count = grid.values().filter(|&&count| count > 1).count();
 
-        Ok(intersection_count)
+        Ok(intersection_count.into())
     }
 }
+
+crate::aoc::example_tests!(Day5);
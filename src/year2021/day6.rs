@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+
+use crate::answer::Answer;
+use crate::aoc::{Example, Solution};
+
+fn parse_fish(input: &str) -> anyhow::Result<HashMap<usize, usize>> {
+    let ages = input
+        .trim()
+        .split(',')
+        .map(|num| num.parse().context("Invalid fish age"));
+    let mut fish = HashMap::new();
+
+    for age in ages {
+        *fish.entry(age?).or_default() += 1;
+    }
+
+    Ok(fish)
+}
+
+fn pass_a_day(fish: HashMap<usize, usize>) -> HashMap<usize, usize> {
+    let mut aged_fish = HashMap::new();
+    for (age, quantity) in fish {
+        if age == 0 {
+            *aged_fish.entry(6).or_default() += quantity;
+            *aged_fish.entry(8).or_default() += quantity;
+        } else {
+            *aged_fish.entry(age - 1).or_default() += quantity;
+        }
+    }
+
+    aged_fish
+}
+
+pub struct Day6;
+
+crate::aoc::register_day! {
+    year: 2021,
+    day: 6,
+    title: "Lanternfish",
+    parts: [One, Two],
+    solution: Day6,
+}
+
+const EXAMPLE: &str = "\
+3,4,3,1,2
+";
+
+impl Solution for Day6 {
+    type Parsed<'a> = HashMap<usize, usize>;
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part1: Some("5934"),
+            part2: Some("26984457539"),
+        }]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        parse_fish(input)
+    }
+
+    fn part1(&self, fish: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        let mut fish = fish.clone();
+
+        for _ in 0..80 {
+            fish = pass_a_day(fish);
+        }
+
+        Ok(fish.values().sum::<usize>().into())
+    }
+
+    fn part2(&self, fish: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        let mut fish = fish.clone();
+
+        for _ in 0..256 {
+            fish = pass_a_day(fish);
+        }
+
+        Ok(fish.values().sum::<usize>().into())
+    }
+}
+
+crate::aoc::example_tests!(Day6);
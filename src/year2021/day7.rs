@@ -1,21 +1,46 @@
 use anyhow::Context;
 
-use crate::aoc::Aoc;
+use crate::answer::Answer;
+use crate::aoc::{Example, Solution};
 
 pub struct Day7;
 
+crate::aoc::register_day! {
+    year: 2021,
+    day: 7,
+    title: "The Treachery of Whales",
+    parts: [One, Two],
+    solution: Day7,
+}
+
 fn parse_positions(input: &str) -> anyhow::Result<Vec<isize>> {
     input
         .trim()
-        .split(",")
+        .split(',')
         .map(|pos| pos.parse().context("Invalid crab position"))
         .collect()
 }
 
-impl Aoc for Day7 {
-    fn part1(&self, input: &str) -> anyhow::Result<usize> {
-        let positions = parse_positions(input)?;
+const EXAMPLE: &str = "\
+16,1,2,0,4,2,7,1,2,14
+";
+
+impl Solution for Day7 {
+    type Parsed<'a> = Vec<isize>;
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part1: Some("37"),
+            part2: Some("168"),
+        }]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        parse_positions(input)
+    }
 
+    fn part1(&self, positions: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
         let min_pos = *positions.iter().min().context("No minimum position")? as usize;
         let max_pos = *positions.iter().max().context("No maximum position")? as usize;
 
@@ -23,18 +48,15 @@ impl Aoc for Day7 {
             .map(|pos| {
                 positions
                     .iter()
-                    .map(|p| (p - (pos as isize)).abs() as usize)
-                    .sum()
+                    .map(|p| p.abs_diff(pos as isize))
+                    .sum::<usize>()
             })
             .min()
+            .map(Answer::from)
             .context("No optimal distance")
     }
 
-    fn part2(&self, input: &str) -> anyhow::Result<usize> {
-        let positions = parse_positions(input)?;
-
-        println!("positions: {:?}", positions.len());
-
+    fn part2(&self, positions: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
         let min_pos = *positions.iter().min().context("No minimum position")? as u128;
         let max_pos = *positions.iter().max().context("No maximum position")? as u128;
 
@@ -42,11 +64,14 @@ impl Aoc for Day7 {
             .filter_map(|pos| {
                 positions.iter().try_fold::<u128, _, _>(0, |sum, p| {
                     let distance = p.checked_sub(pos as isize)?.checked_abs()? as u128;
-                    let cost = distance.checked_mul(distance.checked_sub(1)?)?;
+                    let cost = distance.checked_mul(distance.checked_add(1)?)? / 2;
                     sum.checked_add(cost)
                 })
             })
             .min()
-            .context("No optimal distance")? as usize)
+            .context("No optimal distance")?
+            .into())
     }
 }
+
+crate::aoc::example_tests!(Day7);
@@ -1,9 +1,10 @@
 use std::collections::HashSet;
 
-use crate::aoc::Aoc;
+use crate::answer::Answer;
+use crate::aoc::{Example, Solution};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Signal {
+pub struct Signal {
     segments: HashSet<char>,
 }
 
@@ -73,22 +74,52 @@ fn determine_order_for_input(signals: &[Signal]) -> anyhow::Result<[Signal; 10]>
 
 pub struct Day8;
 
-impl Aoc for Day8 {
-    fn part1(&self, input: &str) -> anyhow::Result<usize> {
-        Ok(input
-            .lines()
-            .flat_map(|line| {
-                line.split_ascii_whitespace()
-                    .skip_while(|word| word != &"|")
-                    .skip(1)
-            })
-            .filter(|word| [2, 3, 4, 7].contains(&word.len()))
-            .count())
+crate::aoc::register_day! {
+    year: 2021,
+    day: 8,
+    title: "Seven Segment Search",
+    parts: [One, Two],
+    solution: Day8,
+}
+
+const EXAMPLE: &str = "\
+be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
+";
+
+impl Solution for Day8 {
+    type Parsed<'a> = Vec<(Vec<Signal>, Vec<Signal>)>;
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part1: Some("26"),
+            part2: Some("61229"),
+        }]
     }
 
-    fn part2(&self, input: &str) -> anyhow::Result<usize> {
-        let signal_groups = parse_signals(input);
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        Ok(parse_signals(input))
+    }
 
+    fn part1(&self, signal_groups: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
+        Ok(signal_groups
+            .iter()
+            .flat_map(|(_inputs, outputs)| outputs)
+            .filter(|output| [2, 3, 4, 7].contains(&output.segments.len()))
+            .count()
+            .into())
+    }
+
+    fn part2(&self, signal_groups: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
         let outputs = signal_groups
             .iter()
             .map(|(inputs, outputs)| {
@@ -99,7 +130,7 @@ impl Aoc for Day8 {
                         order
                             .iter()
                             .enumerate()
-                            .find(|(_index, signal)| &signal.segments == &output.segments)
+                            .find(|(_index, signal)| signal.segments == output.segments)
                             .map(|(index, _signal)| index)
                             .ok_or_else(|| anyhow::anyhow!("Missing digit: {:?}", order))
                     })
@@ -110,6 +141,8 @@ impl Aoc for Day8 {
             })
             .collect::<anyhow::Result<Vec<usize>>>()?;
 
-        Ok(outputs.iter().sum())
+        Ok(outputs.iter().sum::<usize>().into())
     }
 }
+
+crate::aoc::example_tests!(Day8);
@@ -2,9 +2,10 @@ use std::collections::HashSet;
 
 use anyhow::Context;
 
-use crate::aoc::Aoc;
+use crate::answer::Answer;
+use crate::aoc::{Example, Solution};
 
-struct Floor {
+pub struct Floor {
     grid: Vec<Vec<usize>>,
     width: usize,
     height: usize,
@@ -91,19 +92,48 @@ impl Floor {
 
 pub struct Day9;
 
-impl Aoc for Day9 {
-    fn part1(&self, input: &str) -> anyhow::Result<usize> {
-        let floor = Floor::parse(input)?;
+crate::aoc::register_day! {
+    year: 2021,
+    day: 9,
+    title: "Smoke Basin",
+    parts: [One, Two],
+    solution: Day9,
+}
+
+const EXAMPLE: &str = "\
+2199943210
+3987894921
+9856789892
+8767896789
+9899965678
+";
+
+impl Solution for Day9 {
+    type Parsed<'a> = Floor;
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            input: EXAMPLE,
+            part1: Some("15"),
+            part2: Some("1134"),
+        }]
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> anyhow::Result<Self::Parsed<'a>> {
+        Floor::parse(input)
+    }
+
+    fn part1(&self, floor: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
         let basin_centers = floor.basin_centers();
 
         Ok(basin_centers
             .into_iter()
             .map(|(x, y)| floor.grid[y][x] + 1)
-            .sum())
+            .sum::<usize>()
+            .into())
     }
 
-    fn part2(&self, input: &str) -> anyhow::Result<usize> {
-        let floor = Floor::parse(input)?;
+    fn part2(&self, floor: &Self::Parsed<'_>) -> anyhow::Result<Answer> {
         let basin_centers = floor.basin_centers();
 
         let mut basin_sizes = basin_centers
@@ -116,6 +146,9 @@ impl Aoc for Day9 {
             .iter()
             .rev()
             .take(3)
-            .fold(1, |product, size| product * size))
+            .product::<usize>()
+            .into())
     }
 }
+
+crate::aoc::example_tests!(Day9);